@@ -0,0 +1,322 @@
+//! Circuit breaker for the Outline API
+//!
+//! Counts consecutive failed Outline requests (network errors and 5xx
+//! responses) and opens after a threshold, failing further requests at once
+//! instead of letting each one wait for a timeout. After a cooldown a single
+//! probe request is let through: its success closes the circuit, its failure
+//! opens it again.
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::error::{Error, Result};
+
+/// Circuit state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Requests pass, counting consecutive failures
+    Closed { failures: u32 },
+    /// Requests fail fast until the cooldown ends
+    Open { until: Instant },
+    /// One probe request is allowed through
+    HalfOpen { probing: bool },
+}
+
+/// Point-in-time view of a circuit breaker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakerStatus {
+    /// `closed`, `open` or `half_open`
+    pub state: &'static str,
+    /// Consecutive failures while closed
+    pub consecutive_failures: u32,
+    /// Seconds until the next probe while open
+    pub retry_in_secs: Option<u64>,
+    /// Number of times the circuit has opened
+    pub times_opened: u64,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: State,
+    times_opened: u64,
+}
+
+/// Circuit breaker guarding requests to one Outline base URL
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    base_url: String,
+    threshold: u32,
+    cooldown: Duration,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    /// Create a breaker opening after `threshold` consecutive failures and
+    /// probing again after `cooldown`
+    pub fn new(base_url: impl Into<String>, threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            base_url: base_url.into(),
+            threshold: threshold.max(1),
+            cooldown,
+            inner: Mutex::new(Inner {
+                state: State::Closed { failures: 0 },
+                times_opened: 0,
+            }),
+        }
+    }
+
+    /// Create the breaker of the configured Outline URL, unless disabled
+    pub fn from_config(config: &Config) -> Option<Self> {
+        (config.breaker_threshold > 0).then(|| {
+            Self::new(
+                config.outline_api_url.as_str(),
+                config.breaker_threshold,
+                Duration::from_secs(config.breaker_cooldown),
+            )
+        })
+    }
+
+    /// Run `request` unless the circuit is open, recording its outcome
+    pub async fn call<T, Fut>(&self, request: Fut) -> Result<T>
+    where
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let permit = self.acquire()?;
+        let result = request.await;
+        permit.record(result.as_ref().map_or_else(is_failure, |_| false));
+        result
+    }
+
+    /// Current state of the circuit
+    pub fn status(&self) -> BreakerStatus {
+        let inner = self.lock();
+        let (state, consecutive_failures, retry_in_secs) = match inner.state {
+            State::Closed { failures } => ("closed", failures, None),
+            State::Open { until } => (
+                "open",
+                self.threshold,
+                Some(until.saturating_duration_since(Instant::now()).as_secs()),
+            ),
+            State::HalfOpen { .. } => ("half_open", self.threshold, None),
+        };
+        BreakerStatus {
+            state,
+            consecutive_failures,
+            retry_in_secs,
+            times_opened: inner.times_opened,
+        }
+    }
+
+    fn acquire(&self) -> Result<Permit<'_>> {
+        let probe = {
+            let mut inner = self.lock();
+            match inner.state {
+                State::Closed { .. } => false,
+                State::Open { until } if Instant::now() < until => {
+                    return Err(self.unavailable(Some(until)));
+                }
+                State::HalfOpen { probing: true } => return Err(self.unavailable(None)),
+                State::Open { .. } | State::HalfOpen { probing: false } => {
+                    inner.state = State::HalfOpen { probing: true };
+                    true
+                }
+            }
+        };
+        if probe {
+            info!("Outline circuit half-open, probing {}", self.base_url);
+        }
+        Ok(Permit {
+            breaker: self,
+            probe,
+            recorded: false,
+        })
+    }
+
+    fn unavailable(&self, until: Option<Instant>) -> Error {
+        let retry = until.map_or_else(
+            || "a probe request is in progress".to_string(),
+            |until| {
+                format!(
+                    "retrying in {}s",
+                    until
+                        .saturating_duration_since(Instant::now())
+                        .as_secs()
+                        .max(1)
+                )
+            },
+        );
+        Error::Api {
+            status: 503,
+            message: format!(
+                "Outline unavailable: {} failed {} times in a row, {retry}",
+                self.base_url, self.threshold
+            ),
+            body: None,
+        }
+    }
+
+    fn record(&self, probe: bool, failed: bool) {
+        let mut inner = self.lock();
+        let next = match (inner.state, failed) {
+            (State::Closed { failures }, true) if failures + 1 < self.threshold => State::Closed {
+                failures: failures + 1,
+            },
+            // Requests sent before the circuit opened may still finish
+            (State::Open { .. }, _) => return,
+            (State::HalfOpen { .. }, _) if !probe => return,
+            (_, true) => {
+                inner.times_opened += 1;
+                warn!(
+                    "Outline circuit open for {}s after repeated failures of {}",
+                    self.cooldown.as_secs(),
+                    self.base_url
+                );
+                State::Open {
+                    until: Instant::now() + self.cooldown,
+                }
+            }
+            (state, false) => {
+                if matches!(state, State::HalfOpen { .. }) {
+                    info!("Outline circuit closed, {} is reachable", self.base_url);
+                }
+                State::Closed { failures: 0 }
+            }
+        };
+        inner.state = next;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Permission to send one request, releasing the probe slot if dropped unused
+struct Permit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+    recorded: bool,
+}
+
+impl Permit<'_> {
+    fn record(mut self, failed: bool) {
+        self.recorded = true;
+        self.breaker.record(self.probe, failed);
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        // A cancelled probe must not keep the circuit half-open forever
+        if self.probe && !self.recorded {
+            let mut inner = self.breaker.lock();
+            if matches!(inner.state, State::HalfOpen { .. }) {
+                inner.state = State::HalfOpen { probing: false };
+            }
+        }
+    }
+}
+
+/// Whether an error means Outline itself is failing
+///
+/// 501 is excluded: instances without AI answer it for `documents.answerQuestion`.
+const fn is_failure(error: &Error) -> bool {
+    match error {
+        Error::Network { .. } => true,
+        Error::Api { status, .. } => *status >= 500 && *status != 501,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+    }
+
+    fn server_error() -> Result<()> {
+        Err(Error::Api {
+            status: 502,
+            message: "Bad gateway".to_string(),
+            body: None,
+        })
+    }
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        let breaker = CircuitBreaker::new("https://outline.test/api", 2, Duration::from_secs(60));
+        let rt = runtime();
+
+        assert!(rt.block_on(breaker.call(async { server_error() })).is_err());
+        assert_eq!(breaker.status().state, "closed");
+        assert_eq!(breaker.status().consecutive_failures, 1);
+
+        // A client error means Outline answered, which resets the count
+        let not_found: Result<()> = Err(Error::api(404, "Not found"));
+        assert!(rt.block_on(breaker.call(async { not_found })).is_err());
+        assert_eq!(breaker.status().consecutive_failures, 0);
+
+        assert!(rt.block_on(breaker.call(async { server_error() })).is_err());
+        assert!(rt.block_on(breaker.call(async { server_error() })).is_err());
+        let status = breaker.status();
+        assert_eq!(status.state, "open");
+        assert_eq!(status.times_opened, 1);
+        assert!(status.retry_in_secs.is_some());
+
+        let err = rt
+            .block_on(breaker.call(async { Ok::<_, Error>(()) }))
+            .unwrap_err();
+        assert!(err.to_string().contains("Outline unavailable"));
+    }
+
+    #[test]
+    fn test_not_implemented_is_not_a_failure() {
+        let breaker = CircuitBreaker::new("https://outline.test/api", 1, Duration::from_secs(60));
+        let rt = runtime();
+
+        let not_implemented: Result<()> = Err(Error::api(501, "AI answers are not enabled"));
+        assert!(rt
+            .block_on(breaker.call(async { not_implemented }))
+            .is_err());
+        let status = breaker.status();
+        assert_eq!(status.state, "closed");
+        assert_eq!(status.consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_half_open_probe_closes_or_reopens() {
+        let breaker = CircuitBreaker::new("https://outline.test/api", 1, Duration::ZERO);
+        let rt = runtime();
+
+        assert!(rt.block_on(breaker.call(async { server_error() })).is_err());
+        assert_eq!(breaker.status().state, "open");
+
+        // Cooldown over: the probe fails and the circuit opens again
+        assert!(rt.block_on(breaker.call(async { server_error() })).is_err());
+        assert_eq!(breaker.status().times_opened, 2);
+
+        // The next probe succeeds and closes it
+        assert!(rt
+            .block_on(breaker.call(async { Ok::<_, Error>(()) }))
+            .is_ok());
+        assert_eq!(breaker.status().state, "closed");
+    }
+
+    #[test]
+    fn test_dropped_probe_releases_slot() {
+        let breaker = CircuitBreaker::new("https://outline.test/api", 1, Duration::ZERO);
+        assert!(runtime()
+            .block_on(breaker.call(async { server_error() }))
+            .is_err());
+
+        drop(breaker.acquire().unwrap());
+        assert_eq!(breaker.status().state, "half_open");
+        assert!(breaker.acquire().is_ok());
+    }
+}
@@ -0,0 +1,395 @@
+//! Response cache for Outline read endpoints
+//!
+//! Opt-in in-memory cache shared by every client of the process. Entries are
+//! keyed by a hash of the API key, the endpoint and the request body, so users
+//! of the multi-user HTTP mode never see each other's results. A successful
+//! mutating call drops every entry that mentions one of the IDs it touched.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use tracing::debug;
+
+use crate::error::{Error, Result};
+
+/// Cached endpoints and their default time to live in seconds
+const DEFAULT_TTLS: &[(&str, u64)] = &[
+    ("auth.info", 300),
+    ("users.info", 300),
+    ("users.list", 120),
+    ("collections.info", 60),
+    ("collections.list", 60),
+    ("collections.documents", 30),
+    ("documents.info", 30),
+    ("documents.list", 15),
+];
+
+/// Endpoint actions that only read and never invalidate entries
+const READ_ACTIONS: &[&str] = &[
+    "info",
+    "list",
+    "search",
+    "documents",
+    "drafts",
+    "export",
+    "answerQuestion",
+    "redirect",
+];
+
+/// Fields whose values identify the documents and collections an entry covers
+const ID_FIELDS: &[&str] = &[
+    "id",
+    "urlId",
+    "documentId",
+    "collectionId",
+    "parentDocumentId",
+];
+
+/// Entries kept before the oldest ones are evicted
+const MAX_ENTRIES: usize = 1000;
+
+/// API key hash, endpoint and serialized request body of a request
+pub type RequestKey = (u64, String, String);
+
+#[derive(Debug)]
+struct Entry {
+    value: Value,
+    ids: HashSet<String>,
+    stored_at: Instant,
+    expires_at: Instant,
+}
+
+/// Hit and miss counters of a cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Requests answered from the cache
+    pub hits: u64,
+    /// Cacheable requests sent to Outline
+    pub misses: u64,
+    /// Entries currently stored
+    pub entries: usize,
+}
+
+/// Shared cache of Outline API responses
+#[derive(Debug)]
+pub struct ResponseCache {
+    ttls: HashMap<String, Duration>,
+    entries: Mutex<HashMap<RequestKey, Entry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResponseCache {
+    /// Create a cache with the default TTLs, adjusted by `overrides`
+    ///
+    /// Overrides are `(endpoint, seconds)` pairs; `0` disables caching for
+    /// the endpoint.
+    pub fn new(overrides: &[(String, u64)]) -> Self {
+        let mut seconds: HashMap<String, u64> = DEFAULT_TTLS
+            .iter()
+            .map(|(endpoint, ttl)| ((*endpoint).to_string(), *ttl))
+            .collect();
+        seconds.extend(overrides.iter().cloned());
+
+        Self {
+            ttls: seconds
+                .into_iter()
+                .filter(|(_, ttl)| *ttl > 0)
+                .map(|(endpoint, ttl)| (endpoint, Duration::from_secs(ttl)))
+                .collect(),
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Look up a fresh response, counting a hit or miss for cached endpoints
+    pub fn get(&self, api_key: &str, endpoint: &str, body: &Value) -> Option<Value> {
+        if !self.ttls.contains_key(endpoint) {
+            return None;
+        }
+
+        let key = request_key(api_key, endpoint, body);
+        let cached = {
+            let mut entries = self.lock();
+            match entries.get(&key) {
+                Some(entry) if entry.expires_at > Instant::now() => Some(entry.value.clone()),
+                Some(_) => {
+                    entries.remove(&key);
+                    None
+                }
+                None => None,
+            }
+        };
+
+        if cached.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            debug!("Cache hit: {}", endpoint);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            debug!("Cache miss: {}", endpoint);
+        }
+        cached
+    }
+
+    /// Record a successful response
+    ///
+    /// Responses of cached endpoints are stored; mutating calls invalidate
+    /// the entries mentioning the IDs in their request or result.
+    pub fn store(&self, api_key: &str, endpoint: &str, body: &Value, value: &Value) {
+        if let Some(ttl) = self.ttls.get(endpoint) {
+            let mut ids = HashSet::new();
+            collect_ids(body, &mut ids);
+            collect_ids(value, &mut ids);
+
+            let now = Instant::now();
+            let mut entries = self.lock();
+            if entries.len() >= MAX_ENTRIES {
+                evict(&mut entries, now);
+            }
+            entries.insert(
+                request_key(api_key, endpoint, body),
+                Entry {
+                    value: value.clone(),
+                    ids,
+                    stored_at: now,
+                    expires_at: now + *ttl,
+                },
+            );
+        } else if is_mutation(endpoint) {
+            let mut ids = HashSet::new();
+            top_level_ids(body, &mut ids);
+            top_level_ids(&value["data"], &mut ids);
+            self.invalidate(&ids);
+        }
+    }
+
+    /// Drop every entry mentioning one of `ids`, for all API keys
+    pub fn invalidate(&self, ids: &HashSet<String>) {
+        if ids.is_empty() {
+            return;
+        }
+
+        let removed = {
+            let mut entries = self.lock();
+            let before = entries.len();
+            entries.retain(|_, entry| entry.ids.is_disjoint(ids));
+            before - entries.len()
+        };
+        debug!("Cache invalidated {} entries", removed);
+    }
+
+    /// Current hit and miss counters
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.lock().len(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<RequestKey, Entry>> {
+        // A panic while holding the lock cannot leave an entry half-written
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Parse `endpoint=seconds` pairs separated by commas
+///
+/// # Errors
+///
+/// Returns error if a pair has no `=` or the seconds are not a number.
+pub fn parse_ttl_overrides(value: &str) -> Result<Vec<(String, u64)>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (endpoint, seconds) = pair.split_once('=').ok_or_else(|| Error::Config {
+                message: format!("Invalid cache TTL '{pair}', expected endpoint=seconds"),
+                source: None,
+            })?;
+            let seconds = seconds.trim().parse().map_err(|e| {
+                Error::config_with_source(format!("Invalid cache TTL seconds in '{pair}'"), e)
+            })?;
+            Ok((endpoint.trim().to_string(), seconds))
+        })
+        .collect()
+}
+
+/// Identify a request without keeping the API key itself in memory
+pub fn request_key(api_key: &str, endpoint: &str, body: &Value) -> RequestKey {
+    (key_hash(api_key), endpoint.to_string(), body.to_string())
+}
+
+/// Hash of an API key, used to tell users apart
+pub fn key_hash(api_key: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    api_key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Whether an endpoint may change data
+pub fn is_mutation(endpoint: &str) -> bool {
+    endpoint
+        .rsplit('.')
+        .next()
+        .is_some_and(|action| !READ_ACTIONS.contains(&action))
+}
+
+/// Collect ID values anywhere in `value`
+fn collect_ids(value: &Value, ids: &mut HashSet<String>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                match field {
+                    Value::String(id) if ID_FIELDS.contains(&key.as_str()) => {
+                        ids.insert(id.clone());
+                    }
+                    _ => collect_ids(field, ids),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_ids(item, ids);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collect ID values directly on `value`, without descending into it
+fn top_level_ids(value: &Value, ids: &mut HashSet<String>) {
+    for field in ID_FIELDS {
+        if let Some(id) = value[*field].as_str() {
+            ids.insert(id.to_string());
+        }
+    }
+}
+
+/// Drop expired entries, then the oldest ones if the cache is still full
+fn evict(entries: &mut HashMap<RequestKey, Entry>, now: Instant) {
+    entries.retain(|_, entry| entry.expires_at > now);
+
+    while entries.len() >= MAX_ENTRIES {
+        let Some(oldest) = entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.stored_at)
+            .map(|(key, _)| key.clone())
+        else {
+            break;
+        };
+        entries.remove(&oldest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn document(id: &str, collection_id: &str) -> Value {
+        json!({ "data": { "id": id, "collectionId": collection_id, "createdBy": { "id": "u1" } } })
+    }
+
+    #[test]
+    fn test_cache_hit_and_miss() {
+        let cache = ResponseCache::new(&[]);
+        let body = json!({ "id": "d1" });
+
+        assert!(cache.get("key-a", "documents.info", &body).is_none());
+        cache.store("key-a", "documents.info", &body, &document("d1", "c1"));
+        assert_eq!(
+            cache.get("key-a", "documents.info", &body),
+            Some(document("d1", "c1"))
+        );
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+    }
+
+    #[test]
+    fn test_cache_isolated_per_api_key() {
+        let cache = ResponseCache::new(&[]);
+        let body = json!({ "id": "d1" });
+        cache.store("key-a", "documents.info", &body, &document("d1", "c1"));
+
+        assert!(cache.get("key-b", "documents.info", &body).is_none());
+    }
+
+    #[test]
+    fn test_uncached_endpoints_are_ignored() {
+        let cache = ResponseCache::new(&[("documents.info".to_string(), 0)]);
+        let body = json!({ "id": "d1" });
+        cache.store("key-a", "documents.info", &body, &document("d1", "c1"));
+        cache.store("key-a", "documents.search", &body, &json!({ "data": [] }));
+
+        assert!(cache.get("key-a", "documents.info", &body).is_none());
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 0,
+                misses: 0,
+                entries: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_mutation_invalidates_related_entries() {
+        let cache = ResponseCache::new(&[]);
+        let d1 = json!({ "id": "d1" });
+        let d2 = json!({ "id": "d2" });
+        let list = json!({ "collectionId": "c1" });
+        cache.store("key-a", "documents.info", &d1, &document("d1", "c1"));
+        cache.store("key-b", "documents.info", &d2, &document("d2", "c2"));
+        cache.store("key-a", "documents.list", &list, &json!({ "data": [] }));
+
+        // Reads never invalidate
+        cache.store("key-a", "documents.search", &d1, &json!({ "data": [] }));
+        assert_eq!(cache.stats().entries, 3);
+
+        // Moving d1 into c2 touches d1 and c2 but not the list of c1
+        cache.store(
+            "key-b",
+            "documents.move",
+            &json!({ "id": "d1", "collectionId": "c2" }),
+            &json!({ "data": { "documents": [] } }),
+        );
+        assert!(cache.get("key-a", "documents.info", &d1).is_none());
+        assert!(cache.get("key-b", "documents.info", &d2).is_none());
+        assert!(cache.get("key-a", "documents.list", &list).is_some());
+
+        // Updating a user does not drop documents they created
+        cache.store("key-a", "documents.info", &d1, &document("d1", "c1"));
+        cache.store(
+            "key-a",
+            "users.update",
+            &json!({ "name": "Ann" }),
+            &json!({ "data": { "name": "Ann" } }),
+        );
+        assert!(cache.get("key-a", "documents.info", &d1).is_some());
+    }
+
+    #[test]
+    fn test_parse_ttl_overrides() {
+        assert_eq!(
+            parse_ttl_overrides("documents.info=60, users.list=0").unwrap(),
+            vec![
+                ("documents.info".to_string(), 60),
+                ("users.list".to_string(), 0)
+            ]
+        );
+        assert!(parse_ttl_overrides("").unwrap().is_empty());
+        assert!(parse_ttl_overrides("documents.info").is_err());
+        assert!(parse_ttl_overrides("documents.info=soon").is_err());
+    }
+}
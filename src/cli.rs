@@ -20,6 +20,15 @@ pub enum CliCommand {
     Http,
     /// Run in STDIO mode (default)
     Stdio,
+    /// Sync a collection with a local directory and exit
+    Sync {
+        /// Collection ID to mirror
+        collection_id: String,
+        /// Local directory for markdown files
+        directory: String,
+        /// Only report planned changes
+        dry_run: bool,
+    },
     /// Show help (handled internally)
     Help,
     /// Show version (handled internally)  
@@ -47,6 +56,12 @@ pub fn parse_args() -> CliCommand {
             "--http" | "http" => {
                 return CliCommand::Http;
             }
+            "sync" => {
+                return parse_sync_args(&args[2..]).unwrap_or_else(|| {
+                    eprintln!("Usage: {NAME} sync <COLLECTION_ID> <DIRECTORY> [--dry-run]");
+                    std::process::exit(1);
+                });
+            }
             _ => {
                 eprintln!("Unknown argument: {}", args[1]);
                 eprintln!("Use --help for usage information");
@@ -58,6 +73,24 @@ pub fn parse_args() -> CliCommand {
     CliCommand::Stdio
 }
 
+/// Parse `sync` subcommand arguments
+fn parse_sync_args(args: &[String]) -> Option<CliCommand> {
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+    let mut positional = args.iter().filter(|a| !a.starts_with("--"));
+
+    let collection_id = positional.next()?.clone();
+    let directory = positional.next()?.clone();
+    if positional.next().is_some() || args.iter().any(|a| a.starts_with("--") && a != "--dry-run") {
+        return None;
+    }
+
+    Some(CliCommand::Sync {
+        collection_id,
+        directory,
+        dry_run,
+    })
+}
+
 /// Print help information
 fn print_help() {
     println!("{HELP_CONTENT}");
@@ -88,6 +121,23 @@ mod tests {
         assert!(VERSION.contains('.'));
     }
 
+    #[test]
+    fn test_parse_sync_args() {
+        let args = |list: &[&str]| list.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert_eq!(
+            parse_sync_args(&args(&["col-1", "./wiki", "--dry-run"])),
+            Some(CliCommand::Sync {
+                collection_id: "col-1".to_string(),
+                directory: "./wiki".to_string(),
+                dry_run: true,
+            })
+        );
+        assert!(parse_sync_args(&args(&["col-1"])).is_none());
+        assert!(parse_sync_args(&args(&["col-1", "./wiki", "extra"])).is_none());
+        assert!(parse_sync_args(&args(&["col-1", "./wiki", "--force"])).is_none());
+    }
+
     #[test]
     fn test_help_content() {
         // Help content should contain expected sections
@@ -4,6 +4,7 @@
 
 use crate::error::{Error, Result};
 use std::net::IpAddr;
+use std::path::PathBuf;
 use url::Url;
 
 /// Application configuration
@@ -28,6 +29,30 @@ pub struct Config {
     pub http_rate_limit: u32,
     /// Allowed MCP authentication tokens (required for HTTP mode)
     pub mcp_auth_tokens: Vec<String>,
+    /// Maximum characters of a document body in tool results (default: 0 = unlimited)
+    pub max_result_chars: Option<usize>,
+    /// Cache responses of Outline read endpoints (default: false)
+    pub cache_enabled: bool,
+    /// Per-endpoint cache TTL overrides in seconds
+    pub cache_ttls: Vec<(String, u64)>,
+    /// Maximum concurrent Outline requests of the process (default: 32)
+    pub max_concurrency: usize,
+    /// Maximum concurrent Outline requests per API key (default: 8)
+    pub max_concurrency_per_key: usize,
+    /// Seconds a request may wait for a free slot before failing (default: 10)
+    pub queue_timeout: u64,
+    /// Consecutive Outline failures that open the circuit breaker (default: 5, 0 = disabled)
+    pub breaker_threshold: u32,
+    /// Seconds the circuit stays open before a probe request (default: 30)
+    pub breaker_cooldown: u64,
+    /// Service API key for the `/ready` probe calling `auth.info` (optional)
+    pub ready_api_key: Option<ApiKey>,
+    /// Seconds a `/ready` probe result is reused (default: 10)
+    pub ready_cache_ttl: u64,
+    /// Bearer token required by `/metrics` (optional, open when unset)
+    pub metrics_token: Option<String>,
+    /// Connection settings of the Outline API client
+    pub transport: TransportConfig,
 }
 
 impl Config {
@@ -73,6 +98,56 @@ impl Config {
             .filter(|s| !s.is_empty())
             .collect();
 
+        let max_result_chars: usize = std::env::var("OUTLINE_MCP_MAX_RESULT_CHARS")
+            .unwrap_or_else(|_| "0".to_string())
+            .parse()
+            .map_err(|e| Error::config_with_source("Invalid OUTLINE_MCP_MAX_RESULT_CHARS", e))?;
+
+        let cache_enabled = std::env::var("OUTLINE_MCP_CACHE")
+            .is_ok_and(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"));
+
+        let cache_ttls = crate::cache::parse_ttl_overrides(
+            &std::env::var("OUTLINE_MCP_CACHE_TTLS").unwrap_or_default(),
+        )?;
+
+        let max_concurrency: usize = std::env::var("OUTLINE_MCP_MAX_CONCURRENCY")
+            .unwrap_or_else(|_| "32".to_string())
+            .parse()
+            .map_err(|e| Error::config_with_source("Invalid OUTLINE_MCP_MAX_CONCURRENCY", e))?;
+
+        let max_concurrency_per_key: usize = std::env::var("OUTLINE_MCP_MAX_CONCURRENCY_PER_KEY")
+            .unwrap_or_else(|_| "8".to_string())
+            .parse()
+            .map_err(|e| {
+                Error::config_with_source("Invalid OUTLINE_MCP_MAX_CONCURRENCY_PER_KEY", e)
+            })?;
+
+        let queue_timeout: u64 = std::env::var("OUTLINE_MCP_QUEUE_TIMEOUT")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .map_err(|e| Error::config_with_source("Invalid OUTLINE_MCP_QUEUE_TIMEOUT", e))?;
+
+        let breaker_threshold: u32 = std::env::var("OUTLINE_MCP_BREAKER_THRESHOLD")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .map_err(|e| Error::config_with_source("Invalid OUTLINE_MCP_BREAKER_THRESHOLD", e))?;
+
+        let breaker_cooldown: u64 = std::env::var("OUTLINE_MCP_BREAKER_COOLDOWN")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .map_err(|e| Error::config_with_source("Invalid OUTLINE_MCP_BREAKER_COOLDOWN", e))?;
+
+        let ready_api_key = non_empty_var("OUTLINE_MCP_READY_API_KEY")
+            .map(ApiKey::new)
+            .transpose()?;
+
+        let ready_cache_ttl: u64 = std::env::var("OUTLINE_MCP_READY_CACHE_TTL")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .map_err(|e| Error::config_with_source("Invalid OUTLINE_MCP_READY_CACHE_TTL", e))?;
+
+        let metrics_token = non_empty_var("OUTLINE_MCP_METRICS_TOKEN");
+
         Ok(Self {
             outline_api_key,
             outline_api_url: outline_api_url
@@ -91,6 +166,18 @@ impl Config {
             http_session_timeout,
             http_rate_limit,
             mcp_auth_tokens,
+            max_result_chars: (max_result_chars > 0).then_some(max_result_chars),
+            cache_enabled,
+            cache_ttls,
+            max_concurrency,
+            max_concurrency_per_key,
+            queue_timeout,
+            breaker_threshold,
+            breaker_cooldown,
+            ready_api_key,
+            ready_cache_ttl,
+            metrics_token,
+            transport: TransportConfig::from_env()?,
         })
     }
 
@@ -130,10 +217,124 @@ impl Config {
             http_session_timeout: 1800,
             http_rate_limit: 60,
             mcp_auth_tokens: vec![],
+            max_result_chars: Some(50_000),
+            cache_enabled: false,
+            cache_ttls: vec![],
+            max_concurrency: 32,
+            max_concurrency_per_key: 8,
+            queue_timeout: 10,
+            breaker_threshold: 5,
+            breaker_cooldown: 30,
+            ready_api_key: None,
+            ready_cache_ttl: 10,
+            metrics_token: None,
+            transport: TransportConfig::default(),
         }
     }
 }
 
+/// Connection settings of the Outline API client
+///
+/// Without an explicit proxy, the standard `HTTPS_PROXY`, `HTTP_PROXY` and
+/// `NO_PROXY` environment variables apply.
+#[derive(Debug, Clone)]
+pub struct TransportConfig {
+    /// Seconds to wait for a connection to be established (default: 10)
+    pub connect_timeout: u64,
+    /// Seconds to wait for each read from the connection (default: 30)
+    pub read_timeout: u64,
+    /// Proxy for all Outline requests, honoring `NO_PROXY`
+    pub proxy: Option<Url>,
+    /// PEM bundles with extra trusted CA certificates
+    pub ca_certs: Vec<PathBuf>,
+    /// PEM client certificate for mutual TLS
+    pub client_cert: Option<PathBuf>,
+    /// PEM private key of the client certificate (may also live in `client_cert`)
+    pub client_key: Option<PathBuf>,
+    /// Accept any server certificate (development only)
+    pub insecure_skip_verify: bool,
+}
+
+impl Default for TransportConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: 10,
+            read_timeout: 30,
+            proxy: None,
+            ca_certs: vec![],
+            client_cert: None,
+            client_key: None,
+            insecure_skip_verify: false,
+        }
+    }
+}
+
+impl TransportConfig {
+    /// Load transport settings from environment variables
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a timeout or the proxy URL is invalid, or a client key
+    /// is given without a client certificate.
+    pub fn from_env() -> Result<Self> {
+        let connect_timeout: u64 = std::env::var("OUTLINE_MCP_CONNECT_TIMEOUT")
+            .unwrap_or_else(|_| "10".to_string())
+            .parse()
+            .map_err(|e| Error::config_with_source("Invalid OUTLINE_MCP_CONNECT_TIMEOUT", e))?;
+
+        let read_timeout: u64 = std::env::var("OUTLINE_MCP_READ_TIMEOUT")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .map_err(|e| Error::config_with_source("Invalid OUTLINE_MCP_READ_TIMEOUT", e))?;
+
+        let proxy = non_empty_var("OUTLINE_MCP_PROXY")
+            .map(|url| url.parse())
+            .transpose()
+            .map_err(|e| Error::config_with_source("Invalid OUTLINE_MCP_PROXY", e))?;
+
+        let ca_certs = non_empty_var("OUTLINE_MCP_CA_CERTS")
+            .map(|paths| {
+                paths
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|p| !p.is_empty())
+                    .map(PathBuf::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let client_cert = non_empty_var("OUTLINE_MCP_CLIENT_CERT").map(PathBuf::from);
+        let client_key = non_empty_var("OUTLINE_MCP_CLIENT_KEY").map(PathBuf::from);
+        if client_key.is_some() && client_cert.is_none() {
+            return Err(Error::Config {
+                message: "OUTLINE_MCP_CLIENT_KEY requires OUTLINE_MCP_CLIENT_CERT".to_string(),
+                source: None,
+            });
+        }
+
+        let insecure_skip_verify = std::env::var("OUTLINE_MCP_TLS_INSECURE_SKIP_VERIFY")
+            .is_ok_and(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"));
+
+        Ok(Self {
+            connect_timeout,
+            read_timeout,
+            proxy,
+            ca_certs,
+            client_cert,
+            client_key,
+            insecure_skip_verify,
+        })
+    }
+}
+
+/// Value of an environment variable, unless unset or blank
+fn non_empty_var(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
 /// Secure API key wrapper
 #[derive(Debug, Clone)]
 pub struct ApiKey {
@@ -59,6 +59,7 @@ impl AuthGuard {
             bucket.tokens -= 1.0;
             true
         } else {
+            crate::metrics::global().rate_limited();
             false
         }
     }
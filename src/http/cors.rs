@@ -21,12 +21,12 @@ pub fn apply<B>(response: &mut Response<B>) {
     headers.insert(
         hyper::header::ACCESS_CONTROL_ALLOW_HEADERS,
         HeaderValue::from_static(
-            "Content-Type, Accept, Authorization, X-MCP-Token, Mcp-Session-Id",
+            "Content-Type, Accept, Authorization, X-MCP-Token, Mcp-Session-Id, X-Request-Id, traceparent",
         ),
     );
     headers.insert(
         hyper::header::ACCESS_CONTROL_EXPOSE_HEADERS,
-        HeaderValue::from_static("Mcp-Session-Id"),
+        HeaderValue::from_static("Mcp-Session-Id, X-Request-Id"),
     );
 }
 
@@ -67,7 +67,7 @@ mod tests {
             resp.headers()
                 .get(hyper::header::ACCESS_CONTROL_EXPOSE_HEADERS)
                 .unwrap(),
-            "Mcp-Session-Id"
+            "Mcp-Session-Id, X-Request-Id"
         );
     }
 }
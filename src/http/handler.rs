@@ -8,7 +8,7 @@ use std::time::Duration;
 use hyper::body::Incoming;
 use hyper::Response;
 use tokio::sync::mpsc;
-use tracing::{debug, error, warn};
+use tracing::{debug, error, warn, Span};
 
 use super::server::AppState;
 use super::{auth, cors, request, response, sse, HttpBody};
@@ -94,16 +94,29 @@ pub async fn handle_post(
         // No session required for simple requests
         None
     };
+    if let Some(sid) = &response_session_id {
+        Span::current().record("session_id", sid.as_str());
+    }
 
     // Create per-request Outline client
     let outline_client = outline::Client::from_parts(
         state.shared_http_client.clone(),
         api_key,
         state.outline_base_url.clone(),
-    );
+    )
+    .with_cache(state.cache.clone())
+    .with_limiter(Some(Arc::clone(&state.limiter)))
+    .with_breaker(state.breaker.clone());
 
     // Process MCP request
-    match mcp::handle_request(&body_str, &outline_client).await {
+    match mcp::handle_request(
+        &body_str,
+        &outline_client,
+        mcp::Transport::Http,
+        state.max_result_chars,
+    )
+    .await
+    {
         Ok(Some(mcp_response)) => {
             let mut resp = response::ok(&mcp_response);
             if let Some(sid) = response_session_id {
@@ -163,6 +176,7 @@ pub async fn handle_get_sse(
         }
         None => return response::bad_request("Mcp-Session-Id required for SSE"),
     };
+    Span::current().record("session_id", session_id.as_str());
 
     // Create SSE channel
     let (tx, rx) = mpsc::channel::<bytes::Bytes>(32);
@@ -217,6 +231,7 @@ pub async fn handle_delete(
     let Some(session_id) = request::extract_session_id(&req) else {
         return response::bad_request("Mcp-Session-Id required for DELETE");
     };
+    Span::current().record("session_id", session_id.as_str());
 
     if state.sessions.remove(&session_id).await {
         debug!("Deleted session: {}", session_id);
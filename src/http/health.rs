@@ -1,27 +1,105 @@
 //! Health check endpoint
 //!
-//! Provides a simple health check endpoint that returns server status and version.
+//! Provides a simple health check endpoint that returns server status and
+//! version, Outline request concurrency counters, the state of the Outline
+//! circuit breaker, and response cache counters when the cache is enabled.
 
 use hyper::Response;
+use serde_json::json;
 
+use super::server::AppState;
 use super::HttpBody;
 
 /// Handle GET /health request (no authentication required)
-pub fn handle() -> Response<HttpBody> {
-    let body = format!(
-        r#"{{"status":"ok","version":"{}"}}"#,
-        env!("CARGO_PKG_VERSION")
-    );
-    super::response::ok(&body)
+pub fn handle(state: &AppState) -> Response<HttpBody> {
+    let requests = state.limiter.stats();
+    let mut body = json!({
+        "status": "ok",
+        "version": env!("CARGO_PKG_VERSION"),
+        "outline_requests": {
+            "active": requests.active,
+            "coalesced": requests.coalesced,
+            "rejected": requests.rejected
+        }
+    });
+
+    if let Some(breaker) = &state.breaker {
+        let circuit = breaker.status();
+        body["outline"] = json!({
+            "circuit": circuit.state,
+            "consecutive_failures": circuit.consecutive_failures,
+            "retry_in_secs": circuit.retry_in_secs,
+            "times_opened": circuit.times_opened
+        });
+    }
+
+    if let Some(cache) = &state.cache {
+        let counters = cache.stats();
+        body["cache"] = json!({
+            "hits": counters.hits,
+            "misses": counters.misses,
+            "entries": counters.entries
+        });
+    }
+
+    super::response::ok(&body.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::breaker::CircuitBreaker;
+    use crate::cache::ResponseCache;
+    use crate::http::auth::AuthGuard;
+    use crate::http::ready::Readiness;
+    use crate::http::session::SessionManager;
+    use crate::limiter::RequestLimiter;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn state(cache: Option<Arc<ResponseCache>>) -> AppState {
+        AppState {
+            auth: AuthGuard::new(vec![], 60),
+            sessions: SessionManager::new(1800),
+            outline_base_url: "https://test.example.com/api".parse().unwrap(),
+            shared_http_client: reqwest::Client::new(),
+            max_body_size: 1_048_576,
+            max_result_chars: None,
+            cache,
+            limiter: Arc::new(RequestLimiter::new(4, 2, Duration::from_secs(1))),
+            breaker: Some(Arc::new(CircuitBreaker::new(
+                "https://test.example.com/api",
+                5,
+                Duration::from_secs(30),
+            ))),
+            readiness: Readiness::new(None, Duration::from_secs(10)),
+            metrics_token: None,
+        }
+    }
 
     #[test]
     fn test_health_check() {
-        let resp = handle();
+        let resp = handle(&state(None));
         assert_eq!(resp.status(), hyper::StatusCode::OK);
     }
+
+    #[test]
+    fn test_health_check_with_cache() {
+        use http_body_util::BodyExt;
+
+        let resp = handle(&state(Some(Arc::new(ResponseCache::new(&[])))));
+        let body = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(resp.into_body().collect())
+            .unwrap()
+            .to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["cache"]["hits"], 0);
+        assert_eq!(body["outline_requests"]["active"], 0);
+        assert_eq!(body["outline"]["circuit"], "closed");
+        // Unauthenticated callers must not learn where Outline lives
+        assert!(body["outline"].get("url").is_none());
+        assert!(!body.to_string().contains("test.example.com"));
+    }
 }
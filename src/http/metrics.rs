@@ -0,0 +1,102 @@
+//! Prometheus metrics endpoint
+//!
+//! Serves the process-wide metrics in the Prometheus text format, sampling
+//! the number of active sessions at scrape time. When `OUTLINE_MCP_METRICS_TOKEN`
+//! is set, scrapers must send it as a bearer token.
+
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::{HeaderMap, Response};
+
+use super::server::AppState;
+use super::HttpBody;
+use crate::metrics::{self, Gauges};
+
+/// Handle GET /metrics request
+pub async fn handle(headers: &HeaderMap, state: &AppState) -> Response<HttpBody> {
+    if let Some(token) = &state.metrics_token {
+        let presented = headers
+            .get(hyper::header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(str::trim);
+        if presented != Some(token.as_str()) {
+            return super::response::unauthorized("Invalid metrics token");
+        }
+    }
+
+    let gauges = Gauges {
+        sessions: state.sessions.count().await,
+    };
+    Response::builder()
+        .status(hyper::StatusCode::OK)
+        .header(
+            hyper::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )
+        .body(HttpBody::Full(Full::new(Bytes::from(
+            metrics::global().render(gauges),
+        ))))
+        .unwrap_or_else(|_| super::response::internal_server_error())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::auth::AuthGuard;
+    use crate::http::ready::Readiness;
+    use crate::http::session::SessionManager;
+    use crate::limiter::RequestLimiter;
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn app_state(metrics_token: Option<&str>) -> AppState {
+        AppState {
+            auth: AuthGuard::new(vec![], 60),
+            sessions: SessionManager::new(1800),
+            outline_base_url: "https://test.example.com/api".parse().unwrap(),
+            shared_http_client: reqwest::Client::new(),
+            max_body_size: 1_048_576,
+            max_result_chars: None,
+            cache: None,
+            limiter: Arc::new(RequestLimiter::new(4, 2, Duration::from_secs(1))),
+            breaker: None,
+            readiness: Readiness::new(None, Duration::from_secs(10)),
+            metrics_token: metrics_token.map(str::to_string),
+        }
+    }
+
+    fn headers(authorization: Option<&str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(value) = authorization {
+            headers.insert(hyper::header::AUTHORIZATION, value.parse().unwrap());
+        }
+        headers
+    }
+
+    #[tokio::test]
+    async fn test_metrics_text_format() {
+        let state = app_state(None);
+        state.sessions.create().await;
+
+        let resp = handle(&headers(None), &state).await;
+        assert_eq!(resp.status(), hyper::StatusCode::OK);
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        assert!(text.contains("outline_mcp_sessions_active 1"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_token_required() {
+        let state = app_state(Some("scrape-secret"));
+        let resp = handle(&headers(None), &state).await;
+        assert_eq!(resp.status(), hyper::StatusCode::UNAUTHORIZED);
+
+        let resp = handle(&headers(Some("Bearer wrong")), &state).await;
+        assert_eq!(resp.status(), hyper::StatusCode::UNAUTHORIZED);
+
+        let resp = handle(&headers(Some("Bearer scrape-secret")), &state).await;
+        assert_eq!(resp.status(), hyper::StatusCode::OK);
+    }
+}
@@ -7,6 +7,8 @@ mod auth;
 mod cors;
 mod handler;
 mod health;
+mod metrics;
+mod ready;
 mod request;
 mod response;
 mod router;
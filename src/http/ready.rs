@@ -0,0 +1,266 @@
+//! Readiness endpoint
+//!
+//! Unlike `/health`, `/ready` checks that the server can actually do its work:
+//! the configured Outline instance must be reachable, its circuit closed and
+//! the session store responsive. Degraded readiness answers with 503 so that
+//! orchestrators stop routing traffic here.
+
+use std::time::{Duration, Instant};
+
+use hyper::{Response, StatusCode};
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use super::server::AppState;
+use super::HttpBody;
+use crate::config::ApiKey;
+use crate::error::Error;
+use crate::outline;
+
+/// Upper bound for one Outline probe
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Upper bound for checking the session store
+const SESSION_CHECK_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Outcome of probing Outline
+#[derive(Debug, Clone)]
+struct Probe {
+    reachable: bool,
+    detail: String,
+    latency_ms: u128,
+}
+
+/// Cached Outline connectivity check
+#[derive(Debug)]
+pub struct Readiness {
+    service_key: Option<ApiKey>,
+    ttl: Duration,
+    last: Mutex<Option<(Instant, Probe)>>,
+}
+
+impl Readiness {
+    /// Create a readiness check caching probe results for `ttl`
+    ///
+    /// With a `service_key`, the probe calls `auth.info` and requires it to
+    /// succeed; without one, any answer from Outline below 500 counts.
+    pub const fn new(service_key: Option<ApiKey>, ttl: Duration) -> Self {
+        Self {
+            service_key,
+            ttl,
+            last: Mutex::const_new(None),
+        }
+    }
+
+    /// Latest probe result, probing again once the cached one expired
+    async fn probe(&self, state: &AppState) -> (Probe, bool) {
+        // Holding the lock makes concurrent checks share a single probe
+        let mut last = self.last.lock().await;
+        if let Some((at, probe)) = last.as_ref() {
+            if at.elapsed() < self.ttl {
+                return (probe.clone(), true);
+            }
+        }
+
+        let probe = self.probe_outline(state).await;
+        if !probe.reachable {
+            warn!("Readiness probe failed: {}", probe.detail);
+        }
+        *last = Some((Instant::now(), probe.clone()));
+        drop(last);
+        (probe, false)
+    }
+
+    async fn probe_outline(&self, state: &AppState) -> Probe {
+        let started = Instant::now();
+        let probe = async {
+            match &self.service_key {
+                Some(key) => Self::probe_with_key(state, key).await,
+                None => Self::probe_anonymous(state).await,
+            }
+        };
+        let (reachable, detail) = tokio::time::timeout(PROBE_TIMEOUT, probe)
+            .await
+            .unwrap_or_else(|_| {
+                (
+                    false,
+                    format!("no answer within {}s", PROBE_TIMEOUT.as_secs()),
+                )
+            });
+        debug!("Readiness probe: reachable={} ({})", reachable, detail);
+        Probe {
+            reachable,
+            detail,
+            latency_ms: started.elapsed().as_millis(),
+        }
+    }
+
+    async fn probe_with_key(state: &AppState, key: &ApiKey) -> (bool, String) {
+        let client = outline::Client::from_parts(
+            state.shared_http_client.clone(),
+            key.clone(),
+            state.outline_base_url.clone(),
+        );
+        match client.post("auth.info", json!({})).await {
+            Ok(_) => (true, "auth.info succeeded".to_string()),
+            Err(Error::Api { status, .. }) if matches!(status, 401 | 403) => {
+                (false, format!("service key rejected with status {status}"))
+            }
+            Err(e) => (false, e.to_string()),
+        }
+    }
+
+    async fn probe_anonymous(state: &AppState) -> (bool, String) {
+        let url = format!(
+            "{}/auth.info",
+            state.outline_base_url.as_str().trim_end_matches('/')
+        );
+        match state
+            .shared_http_client
+            .post(url)
+            .json(&json!({}))
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_server_error() => (
+                false,
+                format!(
+                    "Outline answered with status {}",
+                    response.status().as_u16()
+                ),
+            ),
+            Ok(response) => (
+                true,
+                format!(
+                    "Outline answered with status {}",
+                    response.status().as_u16()
+                ),
+            ),
+            Err(e) => (false, format!("Outline unreachable: {}", e.without_url())),
+        }
+    }
+}
+
+/// Handle GET /ready request (no authentication required)
+pub async fn handle(state: &AppState) -> Response<HttpBody> {
+    let (probe, cached) = state.readiness.probe(state).await;
+
+    let circuit = state.breaker.as_ref().map(|breaker| breaker.status());
+    let circuit_ok = circuit.as_ref().map_or(true, |c| c.state != "open");
+
+    let sessions = tokio::time::timeout(SESSION_CHECK_TIMEOUT, state.sessions.count())
+        .await
+        .ok();
+
+    let ready = probe.reachable && circuit_ok && sessions.is_some();
+
+    let mut checks = json!({
+        "outline": {
+            "ok": probe.reachable,
+            "detail": probe.detail,
+            "latency_ms": probe.latency_ms,
+            "cached": cached
+        },
+        "sessions": {
+            "ok": sessions.is_some(),
+            "active": sessions
+        }
+    });
+    if let Some(circuit) = circuit {
+        checks["circuit"] = json!({
+            "ok": circuit_ok,
+            "state": circuit.state,
+            "retry_in_secs": circuit.retry_in_secs
+        });
+    }
+
+    let body: Value = json!({
+        "status": if ready { "ready" } else { "degraded" },
+        "checks": checks
+    });
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    super::response::json(status, &body.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::auth::AuthGuard;
+    use crate::http::session::SessionManager;
+    use crate::limiter::RequestLimiter;
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn app_state(base_url: &str) -> AppState {
+        AppState {
+            auth: AuthGuard::new(vec![], 60),
+            sessions: SessionManager::new(1800),
+            outline_base_url: base_url.parse().unwrap(),
+            shared_http_client: reqwest::Client::new(),
+            max_body_size: 1_048_576,
+            max_result_chars: None,
+            cache: None,
+            limiter: Arc::new(RequestLimiter::new(4, 2, Duration::from_secs(1))),
+            breaker: None,
+            readiness: Readiness::new(None, Duration::from_secs(60)),
+            metrics_token: None,
+        }
+    }
+
+    async fn body_of(resp: Response<HttpBody>) -> Value {
+        let bytes = resp.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    /// Outline stand-in answering every request with `status_line`
+    async fn stand_in(status_line: &'static str) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0_u8; 4096];
+                let _ = stream.read(&mut buf).await;
+                let reply = format!("HTTP/1.1 {status_line}\r\ncontent-length: 0\r\n\r\n");
+                let _ = stream.write_all(reply.as_bytes()).await;
+            }
+        });
+        format!("http://{addr}/api")
+    }
+
+    #[tokio::test]
+    async fn test_ready_when_outline_answers() {
+        let state = app_state(&stand_in("401 Unauthorized").await);
+
+        let resp = handle(&state).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = body_of(resp).await;
+        assert_eq!(body["status"], "ready");
+        assert_eq!(body["checks"]["sessions"]["active"], 0);
+        assert_eq!(body["checks"]["outline"]["cached"], false);
+
+        // The second check within the TTL reuses the probe
+        let body = body_of(handle(&state).await).await;
+        assert_eq!(body["checks"]["outline"]["cached"], true);
+    }
+
+    #[tokio::test]
+    async fn test_degraded_when_outline_fails() {
+        let state = app_state(&stand_in("502 Bad Gateway").await);
+        let resp = handle(&state).await;
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body_of(resp).await["status"], "degraded");
+
+        // Nothing listens on port 1
+        let state = app_state("http://127.0.0.1:1/api");
+        let resp = handle(&state).await;
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        // Unauthenticated callers must not learn where Outline lives
+        assert!(!body_of(resp).await.to_string().contains("127.0.0.1"));
+    }
+}
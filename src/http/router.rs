@@ -5,13 +5,22 @@
 use std::convert::Infallible;
 use std::net::IpAddr;
 use std::sync::Arc;
+use std::time::Instant;
 
 use hyper::body::Incoming;
-use hyper::{Method, Response};
-use tracing::debug;
+use hyper::header::HeaderValue;
+use hyper::{HeaderMap, Method, Response};
+use tracing::{debug, field, info_span, Instrument};
 
 use super::server::AppState;
-use super::{cors, handler, health, response, HttpBody};
+use super::{cors, handler, health, metrics, ready, response, HttpBody};
+use crate::{redact, telemetry};
+
+/// Header carrying the request ID
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest request ID accepted from a client
+const MAX_REQUEST_ID_LEN: usize = 128;
 
 /// Route an incoming HTTP request to the appropriate handler
 ///
@@ -26,15 +35,84 @@ pub async fn route(
 ) -> Result<Response<HttpBody>, Infallible> {
     let method = req.method().clone();
     let path = req.uri().path().to_string();
+    let request_id = request_id(req.headers());
+
+    let span = info_span!(
+        "http",
+        request_id = %request_id,
+        method = %method,
+        path = %path,
+        client_ip = %client_ip,
+        session_id = field::Empty,
+    );
+    telemetry::continue_trace(&span, req.headers());
+    async move {
+        debug!("{} {} from {}", method, path, client_ip);
+        debug!("Headers: {}", redact::headers(req.headers()));
+
+        let started = Instant::now();
+        let mut resp = dispatch(req, &method, &path, state, client_ip).await;
+
+        // Known routes only, so that scanners cannot inflate label cardinality
+        let route = match path.as_str() {
+            "/mcp" | "/health" | "/ready" | "/metrics" => path.as_str(),
+            _ => "other",
+        };
+        crate::metrics::global().observe_http(route, resp.status().as_u16(), started.elapsed());
+
+        if let Ok(value) = HeaderValue::from_str(&request_id) {
+            resp.headers_mut().insert(REQUEST_ID_HEADER, value);
+        }
 
-    debug!("{} {} from {}", method, path, client_ip);
+        // Apply CORS to all responses (except preflight which already has them)
+        if method != Method::OPTIONS {
+            cors::apply(&mut resp);
+        }
+
+        Ok(resp)
+    }
+    .instrument(span)
+    .await
+}
+
+/// Request ID from a well-formed `X-Request-Id` header, or a new one
+///
+/// Reusing the client's ID correlates our logs with those of a proxy or agent
+/// in front of us.
+fn request_id(headers: &HeaderMap) -> String {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= MAX_REQUEST_ID_LEN
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+        })
+        .map_or_else(|| uuid::Uuid::new_v4().to_string(), str::to_string)
+}
 
-    let mut resp = match (&method, path.as_str()) {
+/// Call the handler for the method and path
+async fn dispatch(
+    req: hyper::Request<Incoming>,
+    method: &Method,
+    path: &str,
+    state: Arc<AppState>,
+    client_ip: IpAddr,
+) -> Response<HttpBody> {
+    match (method, path) {
         // CORS preflight for any path
         (&Method::OPTIONS, _) => cors::preflight(),
 
         // Health check (no auth)
-        (&Method::GET, "/health") => health::handle(),
+        (&Method::GET, "/health") => health::handle(&state),
+
+        // Readiness check (no auth)
+        (&Method::GET, "/ready") => ready::handle(&state).await,
+
+        // Prometheus metrics (optional token)
+        (&Method::GET, "/metrics") => metrics::handle(req.headers(), &state).await,
 
         // MCP endpoints (with auth)
         (&Method::POST, "/mcp") => handler::handle_post(req, state, client_ip).await,
@@ -44,12 +122,27 @@ pub async fn route(
         // Unknown path
         (_, "/mcp") => response::method_not_allowed(),
         _ => response::not_found(),
-    };
-
-    // Apply CORS to all responses (except preflight which already has them)
-    if method != Method::OPTIONS {
-        cors::apply(&mut resp);
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_id_reused_or_generated() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            REQUEST_ID_HEADER,
+            HeaderValue::from_static("agent-42.step:3"),
+        );
+        assert_eq!(request_id(&headers), "agent-42.step:3");
 
-    Ok(resp)
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("bad id"));
+        let generated = request_id(&headers);
+        assert!(uuid::Uuid::parse_str(&generated).is_ok());
+
+        let other = request_id(&HeaderMap::new());
+        assert_ne!(generated, other);
+    }
 }
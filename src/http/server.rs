@@ -12,10 +12,14 @@ use tracing::{debug, error, info, warn};
 use url::Url;
 
 use super::auth::AuthGuard;
+use super::ready::Readiness;
 use super::router;
 use super::session::SessionManager;
+use crate::breaker::CircuitBreaker;
+use crate::cache::ResponseCache;
 use crate::config::Config;
 use crate::error::Result;
+use crate::limiter::RequestLimiter;
 
 /// Shared application state accessible by all request handlers
 #[derive(Debug)]
@@ -30,6 +34,18 @@ pub struct AppState {
     pub shared_http_client: reqwest::Client,
     /// Maximum allowed request body size in bytes
     pub max_body_size: usize,
+    /// Maximum characters of a document body in tool results
+    pub max_result_chars: Option<usize>,
+    /// Response cache shared by all users, keyed by their Outline API key
+    pub cache: Option<Arc<ResponseCache>>,
+    /// Concurrency limiter shared by all users
+    pub limiter: Arc<RequestLimiter>,
+    /// Circuit breaker of the Outline base URL, unless disabled
+    pub breaker: Option<Arc<CircuitBreaker>>,
+    /// Cached Outline connectivity check for `/ready`
+    pub readiness: Readiness,
+    /// Bearer token required by `/metrics`, if any
+    pub metrics_token: Option<String>,
 }
 
 /// HTTP server with graceful shutdown support
@@ -48,7 +64,7 @@ impl HttpServer {
         let addr = format!("{}:{}", config.http_host, config.http_port.as_u16());
         let listener = TcpListener::bind(&addr).await?;
 
-        let shared_http_client = crate::outline::Client::build_http_client()?;
+        let shared_http_client = crate::outline::Client::build_http_client(&config.transport)?;
 
         let state = Arc::new(AppState {
             auth: AuthGuard::new(config.mcp_auth_tokens.clone(), config.http_rate_limit),
@@ -56,11 +72,23 @@ impl HttpServer {
             outline_base_url: config.outline_api_url.clone(),
             shared_http_client,
             max_body_size: config.http_max_body_size,
+            max_result_chars: config.max_result_chars,
+            cache: config
+                .cache_enabled
+                .then(|| Arc::new(ResponseCache::new(&config.cache_ttls))),
+            limiter: Arc::new(RequestLimiter::from_config(config)),
+            breaker: CircuitBreaker::from_config(config).map(Arc::new),
+            readiness: Readiness::new(
+                config.ready_api_key.clone(),
+                std::time::Duration::from_secs(config.ready_cache_ttl),
+            ),
+            metrics_token: config.metrics_token.clone(),
         });
 
         info!("HTTP server bound to {}", addr);
         info!("Available at POST/GET/DELETE /mcp for MCP requests");
-        info!("Health check at GET /health");
+        info!("Health check at GET /health, readiness check at GET /ready");
+        info!("Prometheus metrics at GET /metrics");
 
         Ok(Self { listener, state })
     }
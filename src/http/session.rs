@@ -80,7 +80,6 @@ impl SessionManager {
     }
 
     /// Get the current number of active sessions
-    #[allow(dead_code)]
     pub async fn count(&self) -> usize {
         self.sessions.read().await.len()
     }
@@ -16,11 +16,18 @@ pub struct SseBody {
 
 impl SseBody {
     /// Create a new SSE body from a channel receiver
-    pub const fn new(rx: mpsc::Receiver<Bytes>) -> Self {
+    pub fn new(rx: mpsc::Receiver<Bytes>) -> Self {
+        crate::metrics::global().sse_stream(1);
         Self { rx }
     }
 }
 
+impl Drop for SseBody {
+    fn drop(&mut self) {
+        crate::metrics::global().sse_stream(-1);
+    }
+}
+
 impl hyper::body::Body for SseBody {
     type Data = Bytes;
     type Error = Infallible;
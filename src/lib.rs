@@ -38,12 +38,20 @@ pub use config::Config;
 pub use error::{Error, Result};
 
 // Modules
+mod breaker;
+mod cache;
 pub mod cli;
 pub mod config;
 pub mod error;
 mod http;
+mod limiter;
+pub mod logging;
 mod mcp;
+mod metrics;
 mod outline;
+mod redact;
+mod sync;
+pub mod telemetry;
 mod tools;
 
 /// Run server in STDIO mode
@@ -56,11 +64,14 @@ mod tools;
 /// Returns error on initialization or request processing problems.
 pub async fn run_stdio(config: Config) -> Result<()> {
     use std::io::{self, Write};
-    use tracing::{debug, error};
+    use tracing::{debug, error, info_span, Instrument};
 
     let stdin = io::stdin();
     let mut stdout = io::stdout();
 
+    let limiter = std::sync::Arc::new(limiter::RequestLimiter::from_config(&config));
+    let breaker = breaker::CircuitBreaker::from_config(&config).map(std::sync::Arc::new);
+
     // STDIO mode requires OUTLINE_API_KEY
     let api_key = config.outline_api_key.ok_or_else(|| Error::Config {
         message: "OUTLINE_API_KEY environment variable required for STDIO mode".to_string(),
@@ -68,7 +79,13 @@ pub async fn run_stdio(config: Config) -> Result<()> {
     })?;
 
     // Initialize Outline API client
-    let outline_client = outline::Client::new(api_key, config.outline_api_url)?;
+    let cache = config
+        .cache_enabled
+        .then(|| std::sync::Arc::new(cache::ResponseCache::new(&config.cache_ttls)));
+    let outline_client = outline::Client::new(api_key, config.outline_api_url, &config.transport)?
+        .with_cache(cache)
+        .with_limiter(Some(limiter))
+        .with_breaker(breaker);
 
     debug!("STDIO server ready");
 
@@ -90,8 +107,17 @@ pub async fn run_stdio(config: Config) -> Result<()> {
             continue;
         }
 
-        // Process JSON-RPC request
-        match mcp::handle_request(&input, &outline_client).await {
+        // Process JSON-RPC request, correlating its log lines by a fresh ID
+        let span = info_span!("rpc", request_id = %uuid::Uuid::new_v4());
+        match mcp::handle_request(
+            &input,
+            &outline_client,
+            mcp::Transport::Stdio,
+            config.max_result_chars,
+        )
+        .instrument(span)
+        .await
+        {
             Ok(Some(response)) => {
                 writeln!(stdout, "{response}")?;
                 stdout.flush()?;
@@ -111,6 +137,45 @@ pub async fn run_stdio(config: Config) -> Result<()> {
     Ok(())
 }
 
+/// Sync a collection with a local directory of markdown files
+///
+/// Pulls remote changes, pushes local edits and writes `.conflict` files when
+/// both sides changed. Prints one line per file to stdout.
+/// Requires `OUTLINE_API_KEY` environment variable to be set.
+///
+/// # Errors
+///
+/// Returns error if the API key is missing, the directory cannot be accessed,
+/// or an Outline API call fails.
+pub async fn run_sync(
+    config: Config,
+    collection_id: String,
+    directory: std::path::PathBuf,
+    dry_run: bool,
+) -> Result<()> {
+    let api_key = config.outline_api_key.ok_or_else(|| Error::Config {
+        message: "OUTLINE_API_KEY environment variable required for sync".to_string(),
+        source: None,
+    })?;
+
+    let outline_client = outline::Client::new(api_key, config.outline_api_url, &config.transport)?;
+    let options = sync::SyncOptions {
+        collection_id,
+        directory,
+        dry_run,
+    };
+
+    let entries = sync::sync_collection(&outline_client, &options).await?;
+    for entry in &entries {
+        println!("{:<16} {}", entry.action.as_str(), entry.file);
+    }
+    if dry_run {
+        println!("Dry run: no files written, no changes pushed");
+    }
+
+    Ok(())
+}
+
 /// Run server in HTTP mode
 ///
 /// Creates a Streamable HTTP server with multi-user support, authentication,
@@ -126,6 +191,24 @@ pub async fn run_stdio(config: Config) -> Result<()> {
 /// - `HTTP_MAX_BODY_SIZE` — max request body in bytes (default: `1048576`)
 /// - `HTTP_SESSION_TIMEOUT` — session TTL in seconds (default: `1800`)
 /// - `HTTP_RATE_LIMIT` — requests per minute per IP (default: `60`)
+/// - `OUTLINE_MCP_MAX_RESULT_CHARS` — max document body characters in tool results (default: `0`, unlimited)
+/// - `OUTLINE_MCP_CACHE` — cache responses of read endpoints per API key (default: `false`)
+/// - `OUTLINE_MCP_CACHE_TTLS` — per-endpoint cache TTLs, e.g. `documents.info=60`
+/// - `OUTLINE_MCP_MAX_CONCURRENCY` — max concurrent Outline requests (default: `32`)
+/// - `OUTLINE_MCP_MAX_CONCURRENCY_PER_KEY` — max concurrent Outline requests per API key (default: `8`)
+/// - `OUTLINE_MCP_QUEUE_TIMEOUT` — seconds to wait for a free request slot (default: `10`)
+/// - `OUTLINE_MCP_BREAKER_THRESHOLD` — consecutive Outline failures that open the circuit (default: `5`, `0` disables)
+/// - `OUTLINE_MCP_BREAKER_COOLDOWN` — seconds before a probe request to a failing Outline (default: `30`)
+/// - `OUTLINE_MCP_READY_API_KEY` — service API key for the `/ready` probe calling `auth.info`
+/// - `OUTLINE_MCP_READY_CACHE_TTL` — seconds a `/ready` probe result is reused (default: `10`)
+/// - `OUTLINE_MCP_METRICS_TOKEN` — bearer token required by `/metrics` (open when unset)
+/// - `OUTLINE_MCP_CONNECT_TIMEOUT`, `OUTLINE_MCP_READ_TIMEOUT` — Outline timeouts in seconds (default: `10`, `30`)
+/// - `OUTLINE_MCP_PROXY` — proxy for Outline requests (default: `HTTPS_PROXY`, honoring `NO_PROXY`)
+/// - `OUTLINE_MCP_CA_CERTS` — comma-separated PEM bundles of extra trusted CAs
+/// - `OUTLINE_MCP_CLIENT_CERT`, `OUTLINE_MCP_CLIENT_KEY` — PEM client certificate and key for mTLS
+/// - `OUTLINE_MCP_TLS_INSECURE_SKIP_VERIFY` — skip TLS verification, development only (default: `false`)
+/// - `LOG_FORMAT` — `json` for one JSON object per log line (default: `text`)
+/// - `OTEL_EXPORTER_OTLP_ENDPOINT` — export spans over OTLP (`otel` feature only)
 ///
 /// # Errors
 ///
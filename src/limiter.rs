@@ -0,0 +1,370 @@
+//! Concurrency limits and request coalescing toward Outline
+//!
+//! Every Outline request waits for a slot in a global semaphore and in a
+//! semaphore of its API key, giving up after the queue timeout. Identical read
+//! requests of the same key that are already in flight are not sent again:
+//! callers wait for the first one and share its result.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::sync::{broadcast, OwnedSemaphorePermit, Semaphore};
+use tracing::debug;
+
+use crate::cache::{is_mutation, key_hash, request_key, RequestKey};
+use crate::config::Config;
+use crate::error::{Error, Result};
+
+/// Result handed to coalesced callers
+type SharedResult = std::result::Result<Value, SharedError>;
+
+/// Cloneable copy of a failed request's error
+#[derive(Debug, Clone)]
+struct SharedError {
+    status: Option<u16>,
+    message: String,
+    body: Option<String>,
+}
+
+impl SharedError {
+    fn from_error(error: &Error) -> Self {
+        match error {
+            Error::Api {
+                status,
+                message,
+                body,
+            } => Self {
+                status: Some(*status),
+                message: message.clone(),
+                body: body.clone(),
+            },
+            other => Self {
+                status: None,
+                message: other.to_string(),
+                body: None,
+            },
+        }
+    }
+
+    fn into_error(self) -> Error {
+        match self.status {
+            Some(status) => Error::Api {
+                status,
+                message: self.message,
+                body: self.body,
+            },
+            None => Error::Internal {
+                message: self.message,
+                context: Some("coalesced Outline request".to_string()),
+            },
+        }
+    }
+}
+
+/// Concurrency counters of a limiter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimiterStats {
+    /// Requests currently holding a global slot
+    pub active: usize,
+    /// Requests answered by joining an identical one in flight
+    pub coalesced: u64,
+    /// Requests rejected after waiting for the queue timeout
+    pub rejected: u64,
+}
+
+/// Shared limiter for all Outline clients of the process
+#[derive(Debug)]
+pub struct RequestLimiter {
+    global: Arc<Semaphore>,
+    global_limit: usize,
+    per_key: Mutex<HashMap<u64, Arc<Semaphore>>>,
+    per_key_limit: usize,
+    queue_timeout: Duration,
+    in_flight: Mutex<HashMap<RequestKey, broadcast::Sender<SharedResult>>>,
+    coalesced: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl RequestLimiter {
+    /// Create a limiter allowing `global_limit` requests in total and
+    /// `per_key_limit` per API key, queueing each for at most `queue_timeout`
+    pub fn new(global_limit: usize, per_key_limit: usize, queue_timeout: Duration) -> Self {
+        let global_limit = global_limit.max(1);
+        Self {
+            global: Arc::new(Semaphore::new(global_limit)),
+            global_limit,
+            per_key: Mutex::new(HashMap::new()),
+            per_key_limit: per_key_limit.max(1),
+            queue_timeout,
+            in_flight: Mutex::new(HashMap::new()),
+            coalesced: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    /// Create a limiter from the concurrency settings of `config`
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            config.max_concurrency,
+            config.max_concurrency_per_key,
+            Duration::from_secs(config.queue_timeout),
+        )
+    }
+
+    /// Run `send` within the limits, sharing the result of identical reads
+    pub async fn run<F, Fut>(
+        &self,
+        api_key: &str,
+        endpoint: &str,
+        body: &Value,
+        send: F,
+    ) -> Result<Value>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Value>>,
+    {
+        if is_mutation(endpoint) {
+            return self.limited(api_key, send).await;
+        }
+
+        let key = request_key(api_key, endpoint, body);
+        let waiting = {
+            let mut in_flight = lock(&self.in_flight);
+            let waiting = in_flight.get(&key).map(broadcast::Sender::subscribe);
+            if waiting.is_none() {
+                in_flight.insert(key.clone(), broadcast::channel(1).0);
+            }
+            waiting
+        };
+
+        if let Some(mut receiver) = waiting {
+            // The first caller may be cancelled, then this one sends on its own
+            if let Ok(shared) = receiver.recv().await {
+                self.coalesced.fetch_add(1, Ordering::Relaxed);
+                debug!("Coalesced request: {}", endpoint);
+                return shared.map_err(SharedError::into_error);
+            }
+            return self.limited(api_key, send).await;
+        }
+
+        let guard = InFlightGuard {
+            in_flight: &self.in_flight,
+            key: Some(key),
+        };
+        let result = self.limited(api_key, send).await;
+        guard.finish(&result);
+        result
+    }
+
+    /// Current concurrency counters
+    pub fn stats(&self) -> LimiterStats {
+        LimiterStats {
+            active: self.global_limit - self.global.available_permits(),
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Run `send` within the limits without coalescing
+    ///
+    /// Used for requests whose results cannot be shared, such as file downloads
+    /// and uploads.
+    pub async fn limited<T, F, Fut>(&self, api_key: &str, send: F) -> Result<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let _permits = self.acquire(api_key).await?;
+        send().await
+    }
+
+    /// Wait for a slot of the key first, then a global one
+    async fn acquire(&self, api_key: &str) -> Result<(OwnedSemaphorePermit, OwnedSemaphorePermit)> {
+        let key_semaphore = {
+            let mut per_key = lock(&self.per_key);
+            // Forget idle keys so the map does not grow with every user seen
+            per_key.retain(|_, semaphore| Arc::strong_count(semaphore) > 1);
+            Arc::clone(
+                per_key
+                    .entry(key_hash(api_key))
+                    .or_insert_with(|| Arc::new(Semaphore::new(self.per_key_limit))),
+            )
+        };
+
+        let permits = tokio::time::timeout(self.queue_timeout, async {
+            let key_permit = key_semaphore.acquire_owned().await;
+            let global_permit = Arc::clone(&self.global).acquire_owned().await;
+            (key_permit, global_permit)
+        })
+        .await;
+
+        if let Ok((Ok(key_permit), Ok(global_permit))) = permits {
+            return Ok((key_permit, global_permit));
+        }
+        self.rejected.fetch_add(1, Ordering::Relaxed);
+        Err(Error::Api {
+            status: 429,
+            message: format!(
+                "Too many concurrent Outline requests, gave up after waiting {}s",
+                self.queue_timeout.as_secs()
+            ),
+            body: None,
+        })
+    }
+}
+
+/// Removes an in-flight entry, also when the first caller is cancelled
+struct InFlightGuard<'a> {
+    in_flight: &'a Mutex<HashMap<RequestKey, broadcast::Sender<SharedResult>>>,
+    key: Option<RequestKey>,
+}
+
+impl InFlightGuard<'_> {
+    fn finish(mut self, result: &Result<Value>) {
+        let sender = self
+            .key
+            .take()
+            .and_then(|key| lock(self.in_flight).remove(&key));
+        if let Some(sender) = sender {
+            let shared = match result {
+                Ok(value) => Ok(value.clone()),
+                Err(error) => Err(SharedError::from_error(error)),
+            };
+            // Nobody may be waiting, which is fine
+            let _ = sender.send(shared);
+        }
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            lock(self.in_flight).remove(&key);
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::AtomicUsize;
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_identical_reads_are_coalesced() {
+        let limiter = RequestLimiter::new(4, 4, Duration::from_secs(1));
+        let calls = AtomicUsize::new(0);
+        let body = json!({ "id": "c1" });
+
+        let send = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok(json!({ "data": "tree" }))
+        };
+
+        let (a, b, other_key) = runtime().block_on(async {
+            tokio::join!(
+                limiter.run("key-a", "collections.documents", &body, send),
+                limiter.run("key-a", "collections.documents", &body, send),
+                limiter.run("key-b", "collections.documents", &body, send),
+            )
+        });
+
+        assert_eq!(a.unwrap(), json!({ "data": "tree" }));
+        assert_eq!(b.unwrap(), json!({ "data": "tree" }));
+        assert!(other_key.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(limiter.stats().coalesced, 1);
+        assert!(lock(&limiter.in_flight).is_empty());
+    }
+
+    #[test]
+    fn test_mutations_are_not_coalesced() {
+        let limiter = RequestLimiter::new(4, 4, Duration::from_secs(1));
+        let calls = AtomicUsize::new(0);
+        let body = json!({ "id": "d1", "title": "New" });
+
+        let send = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Ok(json!({}))
+        };
+
+        let (a, b) = runtime().block_on(async {
+            tokio::join!(
+                limiter.run("key-a", "documents.update", &body, send),
+                limiter.run("key-a", "documents.update", &body, send),
+            )
+        });
+        assert!(a.is_ok() && b.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_coalesced_errors_are_shared() {
+        let limiter = RequestLimiter::new(4, 4, Duration::from_secs(1));
+        let body = json!({ "id": "missing" });
+
+        let send = || async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Err(Error::Api {
+                status: 404,
+                message: "Not found".to_string(),
+                body: None,
+            })
+        };
+
+        let (a, b) = runtime().block_on(async {
+            tokio::join!(
+                limiter.run("key-a", "documents.info", &body, send),
+                limiter.run("key-a", "documents.info", &body, send),
+            )
+        });
+        assert!(matches!(a, Err(Error::Api { status: 404, .. })));
+        assert!(matches!(b, Err(Error::Api { status: 404, .. })));
+    }
+
+    #[test]
+    fn test_per_key_limit_times_out() {
+        let limiter = RequestLimiter::new(4, 1, Duration::from_millis(20));
+
+        let slow = || async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok(json!({}))
+        };
+        let fast = || async { Ok(json!({})) };
+        let bodies = [
+            json!({ "id": "1" }),
+            json!({ "id": "2" }),
+            json!({ "id": "3" }),
+        ];
+
+        let (first, queued, other_key) = runtime().block_on(async {
+            tokio::join!(
+                limiter.run("key-a", "documents.update", &bodies[0], slow),
+                limiter.run("key-a", "documents.update", &bodies[1], fast),
+                limiter.run("key-b", "documents.update", &bodies[2], fast),
+            )
+        });
+
+        assert!(first.is_ok());
+        assert!(matches!(queued, Err(Error::Api { status: 429, .. })));
+        assert!(other_key.is_ok());
+        assert_eq!(limiter.stats().rejected, 1);
+    }
+}
@@ -4,7 +4,9 @@
 
 use tracing::debug;
 
-use outline_mcp_rs::{cli, run_http, run_stdio, Config, Result};
+use outline_mcp_rs::logging::{JsonFields, JsonFormat, LogFormat};
+use outline_mcp_rs::telemetry::Telemetry;
+use outline_mcp_rs::{cli, run_http, run_stdio, run_sync, Config, Result};
 
 /// Application entry point
 #[cfg(not(windows))]
@@ -23,22 +25,28 @@ async fn main_impl() -> Result<()> {
     // Parse CLI arguments first (handles help/version internally)
     let command = cli::parse_args();
 
+    // Load variables from .env file before logging, which reads LOG_FORMAT
+    let dotenv = dotenvy::dotenv();
+
+    // Span export, kept alive until exit so that pending spans get flushed
+    let telemetry = Telemetry::from_env()?;
+
     // Initialize logging based on the command mode
     match command {
         cli::CliCommand::Http => {
             // For HTTP mode, initialize full logging immediately
-            init_logging();
+            init_logging(&telemetry);
         }
-        cli::CliCommand::Stdio => {
+        cli::CliCommand::Stdio | cli::CliCommand::Sync { .. } => {
             // For STDIO mode, initialize minimal logging to stderr only
-            init_stdio_logging();
+            init_stdio_logging(&telemetry);
         }
         // Help and Version are handled in parse_args() and exit
         cli::CliCommand::Help | cli::CliCommand::Version => unreachable!(),
     }
 
-    // Load variables from .env file (ignore errors if file not found)
-    if let Err(e) = dotenvy::dotenv() {
+    // Missing .env file is not an error
+    if let Err(e) = dotenv {
         debug!(
             ".env file not found, using system environment variables: {}",
             e
@@ -46,42 +54,74 @@ async fn main_impl() -> Result<()> {
     } else {
         debug!("Environment loaded from .env file");
     }
+    if telemetry.enabled() {
+        debug!("Exporting spans over OTLP");
+    }
 
     let config = Config::from_env()?;
 
     match command {
         cli::CliCommand::Http => run_http(config).await,
         cli::CliCommand::Stdio => run_stdio(config).await,
+        cli::CliCommand::Sync {
+            collection_id,
+            directory,
+            dry_run,
+        } => run_sync(config, collection_id, directory.into(), dry_run).await,
         // Help and Version are handled in parse_args() and exit
         cli::CliCommand::Help | cli::CliCommand::Version => unreachable!(),
     }
 }
 
 /// Initialize logging for STDIO mode (stderr only, minimal level)
-fn init_stdio_logging() {
+fn init_stdio_logging(telemetry: &Telemetry) {
     use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
     // Use error level by default for STDIO, but allow override with RUST_LOG
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("error"));
 
-    tracing_subscriber::registry()
-        .with(
-            fmt::layer()
-                .with_writer(std::io::stderr) // Force stderr to avoid JSON pollution
-                .with_ansi(false), // Disable colors for cleaner output
-        )
-        .with(filter)
-        .init();
+    let layer = fmt::layer()
+        .with_writer(std::io::stderr) // Force stderr to avoid JSON pollution
+        .with_ansi(false); // Disable colors for cleaner output
+
+    // The filter applies to log lines only; exported spans have their own
+    match LogFormat::from_env() {
+        LogFormat::Json => tracing_subscriber::registry()
+            .with(
+                layer
+                    .event_format(JsonFormat)
+                    .fmt_fields(JsonFields)
+                    .with_filter(filter),
+            )
+            .with(telemetry.layer())
+            .init(),
+        LogFormat::Text => tracing_subscriber::registry()
+            .with(layer.with_filter(filter))
+            .with(telemetry.layer())
+            .init(),
+    }
 }
 
 /// Initialize logging with reasonable defaults (for HTTP mode)
-fn init_logging() {
+fn init_logging(telemetry: &Telemetry) {
     use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
 
-    tracing_subscriber::registry()
-        .with(fmt::layer())
-        .with(filter)
-        .init();
+    match LogFormat::from_env() {
+        LogFormat::Json => tracing_subscriber::registry()
+            .with(
+                fmt::layer()
+                    .with_ansi(false)
+                    .event_format(JsonFormat)
+                    .fmt_fields(JsonFields)
+                    .with_filter(filter),
+            )
+            .with(telemetry.layer())
+            .init(),
+        LogFormat::Text => tracing_subscriber::registry()
+            .with(fmt::layer().with_filter(filter))
+            .with(telemetry.layer())
+            .init(),
+    }
 }
@@ -3,18 +3,42 @@
 //! Simple JSON-RPC 2.0 and MCP protocol implementation without complex abstractions.
 
 use serde_json::{json, Map, Value};
-use tracing::{debug, error};
+use tracing::{debug, error, info_span, Instrument};
 
 use crate::error::{Error, Result};
 use crate::outline::Client as OutlineClient;
+use crate::redact;
 use crate::tools;
 
+/// Transport a request was received through
+///
+/// Some tools touch the local filesystem and are only allowed when the server
+/// runs as a local process for a single user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// Single-user STDIO transport
+    Stdio,
+    /// Multi-user Streamable HTTP transport
+    Http,
+}
+
+impl Transport {
+    /// Whether tools may read or write local files
+    pub const fn allows_local_files(self) -> bool {
+        matches!(self, Self::Stdio)
+    }
+}
+
 /// Handle MCP request
+///
+/// `max_result_chars` limits document bodies in tool results (`None` = unlimited).
 pub async fn handle_request(
     request: &str,
     outline_client: &OutlineClient,
+    transport: Transport,
+    max_result_chars: Option<usize>,
 ) -> Result<Option<String>> {
-    debug!("📨 Received request: {}", request);
+    debug!("📨 Received request: {}", redact::text(request));
 
     // Parse JSON-RPC request
     let request_json: Value = serde_json::from_str(request)?;
@@ -35,39 +59,46 @@ pub async fn handle_request(
     debug!("🔧 Processing method: {}", method);
 
     // Dispatch MCP methods
-    let result = match method {
-        // MCP initialization
-        "initialize" => Ok(Some(handle_initialize(params))),
-
-        // Get tools list
-        "tools/list" => Ok(Some(handle_tools_list(params))),
-
-        // Call tool
-        "tools/call" => handle_tools_call(params, outline_client).await.map(Some),
-
-        // Notifications (no response required)
-        "notifications/initialized" => {
-            debug!("🔔 Client initialization notification received");
-            return Ok(None); // Return None for notifications - no response needed
-        }
-
-        // Unknown method
-        _ => {
-            error!("❌ Unknown method: {}", method);
-            Err(Error::Protocol {
-                protocol: "MCP".to_string(),
-                message: format!("Unknown method: {method}"),
-                code: Some(-32601),
-            })
+    let span = info_span!("mcp", method = %method);
+    let result = async {
+        match method {
+            // MCP initialization
+            "initialize" => Ok(Some(handle_initialize(params))),
+
+            // Get tools list
+            "tools/list" => Ok(Some(handle_tools_list(params, transport))),
+
+            // Call tool
+            "tools/call" => handle_tools_call(params, outline_client, transport, max_result_chars)
+                .await
+                .map(Some),
+
+            // Notifications (no response required)
+            "notifications/initialized" => {
+                debug!("🔔 Client initialization notification received");
+                Ok(None) // Return None for notifications - no response needed
+            }
+
+            // Unknown method
+            _ => {
+                error!("❌ Unknown method: {}", method);
+                Err(Error::Protocol {
+                    protocol: "MCP".to_string(),
+                    message: format!("Unknown method: {method}"),
+                    code: Some(-32601),
+                })
+            }
         }
-    };
+    }
+    .instrument(span)
+    .await;
 
     // Create JSON-RPC response
     match result {
         Ok(Some(result_value)) => {
             let response = create_success_response(id.as_ref(), &result_value);
             let response_str = serde_json::to_string(&response)?;
-            debug!("📤 Sending response: {}", response_str);
+            debug!("📤 Sending response: {}", redact::json(&response));
             Ok(Some(response_str))
         }
         Ok(None) => {
@@ -77,7 +108,7 @@ pub async fn handle_request(
         Err(error) => {
             let response = create_error_response_with_id(id.as_ref(), &error);
             let response_str = serde_json::to_string(&response)?;
-            debug!("📤 Sending error response: {}", response_str);
+            debug!("📤 Sending error response: {}", redact::json(&response));
             Ok(Some(response_str))
         }
     }
@@ -161,10 +192,10 @@ fn handle_initialize(_params: Value) -> Value {
 }
 
 /// Handle tools list request
-fn handle_tools_list(_params: Value) -> Value {
+fn handle_tools_list(_params: Value, transport: Transport) -> Value {
     debug!("📋 Getting tools list");
 
-    let tools_list = tools::get_tools_list();
+    let tools_list = tools::get_tools_list(transport);
 
     json!({
         "tools": tools_list
@@ -172,7 +203,12 @@ fn handle_tools_list(_params: Value) -> Value {
 }
 
 /// Handle tool call
-async fn handle_tools_call(params: Value, outline_client: &OutlineClient) -> Result<Value> {
+async fn handle_tools_call(
+    params: Value,
+    outline_client: &OutlineClient,
+    transport: Transport,
+    max_result_chars: Option<usize>,
+) -> Result<Value> {
     // Extract tool name and arguments
     let name = params
         .get("name")
@@ -189,8 +225,10 @@ async fn handle_tools_call(params: Value, outline_client: &OutlineClient) -> Res
         .unwrap_or_else(|| Value::Object(Map::new()));
 
     debug!("🔨 Calling tool: {}", name);
-    debug!("📊 Arguments: {}", arguments);
+    debug!("📊 Arguments: {}", redact::json(&arguments));
 
     // Call appropriate tool
-    tools::call_tool(name, arguments, outline_client).await
+    tools::call_tool(name, arguments, outline_client, transport, max_result_chars)
+        .instrument(info_span!("tool", tool = name))
+        .await
 }
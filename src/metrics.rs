@@ -0,0 +1,306 @@
+//! Prometheus metrics
+//!
+//! A small process-wide registry of counters and latency histograms, rendered
+//! in the Prometheus text exposition format by the HTTP `/metrics` endpoint.
+//! Like logging, recording goes through a global so that tools and the
+//! Outline client need no extra plumbing.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::Duration;
+
+/// Upper bounds of the latency histogram buckets in seconds
+const BUCKETS: [f64; 12] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+/// Latency histogram with cumulative Prometheus buckets
+#[derive(Debug, Default, Clone)]
+struct Histogram {
+    buckets: [u64; BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        for (bucket, bound) in self.buckets.iter_mut().zip(BUCKETS) {
+            if secs <= bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += secs;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        for (count, bound) in self.buckets.iter().zip(BUCKETS) {
+            let _ = writeln!(out, "{name}_bucket{{{labels},le=\"{bound}\"}} {count}");
+        }
+        let _ = writeln!(out, "{name}_bucket{{{labels},le=\"+Inf\"}} {}", self.count);
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{labels}}} {}", self.count);
+    }
+}
+
+/// Calls of one tool
+#[derive(Debug, Default, Clone)]
+struct ToolStats {
+    errors: u64,
+    latency: Histogram,
+}
+
+impl ToolStats {
+    fn record(&mut self, failed: bool, elapsed: Duration) {
+        self.latency.observe(elapsed);
+        if failed {
+            self.errors += 1;
+        }
+    }
+}
+
+/// Process-wide metrics registry
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// HTTP requests by (route, status)
+    http: Mutex<BTreeMap<(String, String), Histogram>>,
+    /// Tool calls by tool name
+    tools: Mutex<BTreeMap<String, ToolStats>>,
+    /// Outline API requests by (endpoint, status)
+    outline: Mutex<BTreeMap<(String, String), Histogram>>,
+    rate_limited: AtomicU64,
+    sse_streams: AtomicI64,
+}
+
+/// Values sampled at scrape time rather than recorded
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Gauges {
+    /// Active MCP sessions
+    pub sessions: usize,
+}
+
+/// The process-wide registry
+pub fn global() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::default)
+}
+
+impl Metrics {
+    /// Record a handled HTTP request
+    pub fn observe_http(&self, route: &str, status: u16, elapsed: Duration) {
+        lock(&self.http)
+            .entry((route.to_string(), status.to_string()))
+            .or_default()
+            .observe(elapsed);
+    }
+
+    /// Record a tool call and whether it failed
+    pub fn observe_tool(&self, tool: &str, failed: bool, elapsed: Duration) {
+        lock(&self.tools)
+            .entry(tool.to_string())
+            .or_default()
+            .record(failed, elapsed);
+    }
+
+    /// Record a request sent to Outline; `status` is the HTTP status or a
+    /// short error kind such as `network_error`
+    pub fn observe_outline(&self, endpoint: &str, status: &str, elapsed: Duration) {
+        lock(&self.outline)
+            .entry((endpoint.to_string(), status.to_string()))
+            .or_default()
+            .observe(elapsed);
+    }
+
+    /// Count a request rejected by the per-IP rate limit
+    pub fn rate_limited(&self) {
+        self.rate_limited.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Track an SSE stream being opened (`1`) or closed (`-1`)
+    pub fn sse_stream(&self, delta: i64) {
+        self.sse_streams.fetch_add(delta, Ordering::Relaxed);
+    }
+
+    /// Render all metrics in the Prometheus text format
+    pub fn render(&self, gauges: Gauges) -> String {
+        let http = labelled(&lock(&self.http), "route", "status");
+        let outline = labelled(&lock(&self.outline), "endpoint", "status");
+        let tools: Vec<(String, ToolStats)> = lock(&self.tools)
+            .iter()
+            .map(|(tool, stats)| (format!("tool=\"{}\"", escape(tool)), stats.clone()))
+            .collect();
+
+        let mut out = String::new();
+        samples(
+            &mut out,
+            ("outline_mcp_http_requests_total", "counter"),
+            "HTTP requests by route and status",
+            http.iter().map(|(labels, h)| (labels.as_str(), h.count)),
+        );
+        histograms(
+            &mut out,
+            "outline_mcp_http_request_duration_seconds",
+            "HTTP request latency by route and status",
+            http.iter().map(|(labels, h)| (labels.as_str(), h)),
+        );
+        samples(
+            &mut out,
+            ("outline_mcp_tool_calls_total", "counter"),
+            "Tool calls by tool name",
+            tools
+                .iter()
+                .map(|(labels, t)| (labels.as_str(), t.latency.count)),
+        );
+        samples(
+            &mut out,
+            ("outline_mcp_tool_errors_total", "counter"),
+            "Failed tool calls by tool name",
+            tools.iter().map(|(labels, t)| (labels.as_str(), t.errors)),
+        );
+        histograms(
+            &mut out,
+            "outline_mcp_tool_duration_seconds",
+            "Tool call latency by tool name",
+            tools
+                .iter()
+                .map(|(labels, t)| (labels.as_str(), &t.latency)),
+        );
+        samples(
+            &mut out,
+            ("outline_mcp_upstream_requests_total", "counter"),
+            "Outline API requests by endpoint and status",
+            outline.iter().map(|(labels, h)| (labels.as_str(), h.count)),
+        );
+        histograms(
+            &mut out,
+            "outline_mcp_upstream_request_duration_seconds",
+            "Outline API latency by endpoint and status",
+            outline.iter().map(|(labels, h)| (labels.as_str(), h)),
+        );
+        samples(
+            &mut out,
+            ("outline_mcp_sessions_active", "gauge"),
+            "Active MCP sessions",
+            [("", gauges.sessions)],
+        );
+        samples(
+            &mut out,
+            ("outline_mcp_rate_limited_total", "counter"),
+            "Requests rejected by the per-IP rate limit",
+            [("", self.rate_limited.load(Ordering::Relaxed))],
+        );
+        samples(
+            &mut out,
+            ("outline_mcp_sse_streams_open", "gauge"),
+            "Open SSE streams",
+            [("", self.sse_streams.load(Ordering::Relaxed))],
+        );
+        out
+    }
+}
+
+/// Histograms keyed by two label values, with their label strings
+fn labelled(
+    series: &BTreeMap<(String, String), Histogram>,
+    first: &str,
+    second: &str,
+) -> Vec<(String, Histogram)> {
+    series
+        .iter()
+        .map(|((a, b), histogram)| {
+            (
+                format!("{first}=\"{}\",{second}=\"{}\"", escape(a), escape(b)),
+                histogram.clone(),
+            )
+        })
+        .collect()
+}
+
+/// Write a counter or gauge family
+fn samples<'a, V: std::fmt::Display>(
+    out: &mut String,
+    (name, kind): (&str, &str),
+    help: &str,
+    series: impl IntoIterator<Item = (&'a str, V)>,
+) {
+    header(out, name, kind, help);
+    for (labels, value) in series {
+        if labels.is_empty() {
+            let _ = writeln!(out, "{name} {value}");
+        } else {
+            let _ = writeln!(out, "{name}{{{labels}}} {value}");
+        }
+    }
+}
+
+/// Write a histogram family
+fn histograms<'a>(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    series: impl IntoIterator<Item = (&'a str, &'a Histogram)>,
+) {
+    header(out, name, "histogram", help);
+    for (labels, histogram) in series {
+        histogram.render(out, name, labels);
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+/// Escape a label value
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_prometheus_text() {
+        let metrics = Metrics::default();
+        metrics.observe_http("/mcp", 200, Duration::from_millis(30));
+        metrics.observe_tool("get_document", false, Duration::from_millis(20));
+        metrics.observe_tool("get_document", true, Duration::from_secs(2));
+        metrics.observe_outline("documents.info", "200", Duration::from_millis(15));
+        metrics.rate_limited();
+        metrics.sse_stream(1);
+
+        let text = metrics.render(Gauges { sessions: 3 });
+        assert!(text.contains("outline_mcp_http_requests_total{route=\"/mcp\",status=\"200\"} 1"));
+        assert!(text.contains(
+            "outline_mcp_http_request_duration_seconds_bucket{route=\"/mcp\",status=\"200\",le=\"0.05\"} 1"
+        ));
+        assert!(text.contains(
+            "outline_mcp_http_request_duration_seconds_bucket{route=\"/mcp\",status=\"200\",le=\"0.025\"} 0"
+        ));
+        assert!(text.contains("outline_mcp_tool_calls_total{tool=\"get_document\"} 2"));
+        assert!(text.contains("outline_mcp_tool_errors_total{tool=\"get_document\"} 1"));
+        assert!(text.contains(
+            "outline_mcp_upstream_requests_total{endpoint=\"documents.info\",status=\"200\"} 1"
+        ));
+        assert!(text.contains("outline_mcp_sessions_active 3"));
+        assert!(text.contains("outline_mcp_rate_limited_total 1"));
+        assert!(text.contains("outline_mcp_sse_streams_open 1"));
+        assert!(text.contains("# TYPE outline_mcp_tool_duration_seconds histogram"));
+    }
+
+    #[test]
+    fn test_escape_label_values() {
+        assert_eq!(escape("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
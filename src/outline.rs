@@ -2,13 +2,22 @@
 //!
 //! Simple HTTP client for Outline Knowledge Base API.
 
-use reqwest::{header, Client as HttpClient};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use reqwest::{header, multipart, Certificate, Client as HttpClient, Identity, NoProxy, Proxy};
 use serde_json::Value;
-use tracing::debug;
+use tracing::{debug, field, info_span, warn, Instrument, Span};
 use url::Url;
 
-use crate::config::ApiKey;
+use crate::breaker::CircuitBreaker;
+use crate::cache::ResponseCache;
+use crate::config::{ApiKey, TransportConfig};
 use crate::error::{Error, Result};
+use crate::limiter::RequestLimiter;
+use crate::{redact, telemetry};
 
 /// Outline API client
 #[derive(Debug, Clone)]
@@ -19,17 +28,26 @@ pub struct Client {
     api_key: ApiKey,
     /// Base API URL
     base_url: Url,
+    /// Shared response cache, if enabled
+    cache: Option<Arc<ResponseCache>>,
+    /// Shared concurrency limiter, if set
+    limiter: Option<Arc<RequestLimiter>>,
+    /// Shared circuit breaker of the base URL, if set
+    breaker: Option<Arc<CircuitBreaker>>,
 }
 
 impl Client {
     /// Create new Outline API client
-    pub fn new(api_key: ApiKey, base_url: Url) -> Result<Self> {
-        let http_client = Self::build_http_client()?;
+    pub fn new(api_key: ApiKey, base_url: Url, transport: &TransportConfig) -> Result<Self> {
+        let http_client = Self::build_http_client(transport)?;
 
         Ok(Self {
             http: http_client,
             api_key,
             base_url,
+            cache: None,
+            limiter: None,
+            breaker: None,
         })
     }
 
@@ -37,17 +55,60 @@ impl Client {
     ///
     /// # Errors
     ///
-    /// Returns error if the HTTP client cannot be built.
-    pub fn build_http_client() -> Result<HttpClient> {
-        HttpClient::builder()
+    /// Returns error if a CA bundle or client certificate cannot be read or
+    /// parsed, or the HTTP client cannot be built.
+    pub fn build_http_client(transport: &TransportConfig) -> Result<HttpClient> {
+        let mut builder = HttpClient::builder()
             .user_agent(format!(
                 "{}/{}",
                 env!("CARGO_PKG_NAME"),
                 env!("CARGO_PKG_VERSION")
             ))
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .map_err(Into::into)
+            .connect_timeout(Duration::from_secs(transport.connect_timeout))
+            .read_timeout(Duration::from_secs(transport.read_timeout));
+
+        if let Some(proxy_url) = &transport.proxy {
+            let proxy = Proxy::all(proxy_url.as_str())
+                .map_err(|e| Error::config_with_source("Invalid OUTLINE_MCP_PROXY", e))?
+                .no_proxy(NoProxy::from_env());
+            builder = builder.proxy(proxy);
+        }
+
+        for path in &transport.ca_certs {
+            let pem = read_pem(path)?;
+            let certificates = Certificate::from_pem_bundle(&pem).map_err(|e| {
+                Error::config_with_source(format!("Invalid CA bundle {}", path.display()), e)
+            })?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        if let Some(cert_path) = &transport.client_cert {
+            let mut pem = read_pem(cert_path)?;
+            if let Some(key_path) = &transport.client_key {
+                pem.push(b'\n');
+                pem.extend(read_pem(key_path)?);
+            }
+            let identity = Identity::from_pem(&pem).map_err(|e| {
+                Error::config_with_source(
+                    format!("Invalid client certificate {}", cert_path.display()),
+                    e,
+                )
+            })?;
+            builder = builder.identity(identity);
+        }
+
+        if transport.insecure_skip_verify {
+            warn!(
+                "⚠️ OUTLINE_MCP_TLS_INSECURE_SKIP_VERIFY is set: TLS certificates of Outline \
+                 are NOT verified. Anyone on the network path can read and alter traffic, \
+                 including API keys. Never use this outside development."
+            );
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        builder.build().map_err(Into::into)
     }
 
     /// Create client from pre-built HTTP client, API key, and base URL
@@ -59,25 +120,88 @@ impl Client {
             http,
             api_key,
             base_url,
+            cache: None,
+            limiter: None,
+            breaker: None,
         }
     }
 
-    /// Execute POST request to Outline API
-    pub async fn post(&self, endpoint: &str, body: Value) -> Result<Value> {
+    /// Base URL of the Outline API
+    pub const fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// Use a shared response cache for read endpoints
+    #[must_use]
+    pub fn with_cache(mut self, cache: Option<Arc<ResponseCache>>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Bound concurrent requests and coalesce identical reads with a shared limiter
+    #[must_use]
+    pub fn with_limiter(mut self, limiter: Option<Arc<RequestLimiter>>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Fail fast with a shared circuit breaker while Outline is down
+    #[must_use]
+    pub fn with_breaker(mut self, breaker: Option<Arc<CircuitBreaker>>) -> Self {
+        self.breaker = breaker;
+        self
+    }
+
+    /// Build full endpoint URL from the base API URL
+    fn endpoint_url(&self, endpoint: &str) -> Result<Url> {
         // Ensure base_url ends with a slash for proper joining
         let mut url_string = self.base_url.to_string();
         if !url_string.ends_with('/') {
             url_string.push('/');
         }
         url_string.push_str(endpoint);
-        let url = url_string.parse::<url::Url>().map_err(|e| Error::Config {
+        url_string.parse::<url::Url>().map_err(|e| Error::Config {
             message: format!("Invalid URL: {url_string}"),
             source: Some(Box::new(e)),
-        })?;
+        })
+    }
+
+    /// Execute POST request to Outline API
+    ///
+    /// Read endpoints are answered from the cache when one is set; successful
+    /// mutations invalidate the cached responses they affect. With a limiter,
+    /// identical reads already in flight share one request.
+    pub async fn post(&self, endpoint: &str, body: Value) -> Result<Value> {
+        if let Some(cached) = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.get(self.api_key.as_str(), endpoint, &body))
+        {
+            return Ok(cached);
+        }
 
-        debug!("📤 POST request: {} | Body: {}", url, body);
+        let send = || self.guarded(self.send_json(endpoint, &body));
+        let value = match &self.limiter {
+            Some(limiter) => {
+                limiter
+                    .run(self.api_key.as_str(), endpoint, &body, send)
+                    .await?
+            }
+            None => send().await?,
+        };
+        if let Some(cache) = &self.cache {
+            cache.store(self.api_key.as_str(), endpoint, &body, &value);
+        }
+        Ok(value)
+    }
 
-        let response = self
+    /// Send a JSON POST request without cache, limiter or circuit breaker
+    async fn send_json(&self, endpoint: &str, body: &Value) -> Result<Value> {
+        let url = self.endpoint_url(endpoint)?;
+
+        debug!("📤 POST request: {} | Body: {}", url, redact::json(body));
+
+        let request = self
             .http
             .post(url.clone())
             .header(
@@ -85,26 +209,213 @@ impl Client {
                 format!("Bearer {}", self.api_key.as_str()),
             )
             .header(header::CONTENT_TYPE, "application/json")
-            .json(&body)
-            .send()
-            .await?;
+            .json(body);
+        let response = send_upstream(endpoint, request).await?;
 
         self.handle_response(response).await
     }
 
+    /// Wait for the limiter, if any, before running `send` through the breaker
+    async fn limited<T, F, Fut>(&self, send: F) -> Result<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        match &self.limiter {
+            Some(limiter) => {
+                limiter
+                    .limited(self.api_key.as_str(), || self.guarded(send()))
+                    .await
+            }
+            None => self.guarded(send()).await,
+        }
+    }
+
+    /// Fail fast while the circuit breaker, if any, is open
+    async fn guarded<T, Fut>(&self, request: Fut) -> Result<T>
+    where
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        match &self.breaker {
+            Some(breaker) => breaker.call(request).await,
+            None => request.await,
+        }
+    }
+
+    /// Execute POST request and return the raw response body with its content type
+    ///
+    /// Used for endpoints that respond with files (or redirects to them)
+    /// instead of JSON, such as `documents.export` and `fileOperations.redirect`.
+    pub async fn post_bytes(
+        &self,
+        endpoint: &str,
+        body: Value,
+        accept: &str,
+    ) -> Result<(String, Bytes)> {
+        let url = self.endpoint_url(endpoint)?;
+
+        debug!(
+            "📤 POST request: {} | Accept: {} | Body: {}",
+            url,
+            accept,
+            redact::json(&body)
+        );
+
+        let (content_type, bytes) = self
+            .limited(|| async {
+                let request = self
+                    .http
+                    .post(url)
+                    .header(
+                        header::AUTHORIZATION,
+                        format!("Bearer {}", self.api_key.as_str()),
+                    )
+                    .header(header::ACCEPT, accept)
+                    .json(&body);
+                let response = send_upstream(endpoint, request).await?;
+
+                if !response.status().is_success() {
+                    return Err(Self::error_from_response(response).await);
+                }
+
+                let content_type = response
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or("application/octet-stream")
+                    .to_string();
+                Ok((content_type, response.bytes().await?))
+            })
+            .await?;
+
+        debug!("✅ Response: {} bytes of {}", bytes.len(), content_type);
+
+        Ok((content_type, bytes))
+    }
+
+    /// Execute POST request and hand the response body to `sink` chunk by chunk
+    ///
+    /// Returns the content type. Used for downloads that should not be held in
+    /// memory whole, such as collection export archives; an error from `sink`
+    /// aborts the download.
+    pub async fn post_streamed<F>(
+        &self,
+        endpoint: &str,
+        body: Value,
+        accept: &str,
+        mut sink: F,
+    ) -> Result<String>
+    where
+        F: FnMut(&[u8]) -> Result<()>,
+    {
+        let url = self.endpoint_url(endpoint)?;
+
+        debug!(
+            "📤 POST request: {} | Accept: {} | Body: {}",
+            url,
+            accept,
+            redact::json(&body)
+        );
+
+        let (content_type, length) = self
+            .limited(|| async move {
+                let request = self
+                    .http
+                    .post(url)
+                    .header(
+                        header::AUTHORIZATION,
+                        format!("Bearer {}", self.api_key.as_str()),
+                    )
+                    .header(header::ACCEPT, accept)
+                    .json(&body);
+                let mut response = send_upstream(endpoint, request).await?;
+
+                if !response.status().is_success() {
+                    return Err(Self::error_from_response(response).await);
+                }
+
+                let content_type = response
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or("application/octet-stream")
+                    .to_string();
+                let mut length = 0;
+                while let Some(chunk) = response.chunk().await? {
+                    length += chunk.len();
+                    sink(&chunk)?;
+                }
+                Ok((content_type, length))
+            })
+            .await?;
+
+        debug!("✅ Response: {} bytes of {}", length, content_type);
+
+        Ok(content_type)
+    }
+
+    /// Execute multipart POST request uploading a single file
+    ///
+    /// String, number and boolean values of `fields` are sent as text parts
+    /// alongside the `file` part.
+    pub async fn post_multipart(
+        &self,
+        endpoint: &str,
+        fields: Value,
+        file_name: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<Value> {
+        let url = self.endpoint_url(endpoint)?;
+
+        debug!(
+            "📤 POST multipart request: {} | Fields: {} | File: {} ({} bytes)",
+            url,
+            redact::json(&fields),
+            file_name,
+            data.len()
+        );
+
+        let file_part = multipart::Part::bytes(data)
+            .file_name(file_name.to_string())
+            .mime_str(content_type)?;
+        let mut form = multipart::Form::new().part("file", file_part);
+
+        if let Value::Object(map) = &fields {
+            for (key, value) in map {
+                let text = match value {
+                    Value::String(s) => s.clone(),
+                    Value::Null => continue,
+                    other => other.to_string(),
+                };
+                form = form.text(key.clone(), text);
+            }
+        }
+
+        let value = self
+            .limited(|| async {
+                let request = self
+                    .http
+                    .post(url)
+                    .header(
+                        header::AUTHORIZATION,
+                        format!("Bearer {}", self.api_key.as_str()),
+                    )
+                    .multipart(form);
+                let response = send_upstream(endpoint, request).await?;
+                self.handle_response(response).await
+            })
+            .await?;
+        if let Some(cache) = &self.cache {
+            cache.store(self.api_key.as_str(), endpoint, &fields, &value);
+        }
+        Ok(value)
+    }
+
     /// Execute GET request to Outline API
     #[allow(dead_code)]
     pub async fn get(&self, endpoint: &str) -> Result<Value> {
-        // Ensure base_url ends with a slash for proper joining
-        let mut url_string = self.base_url.to_string();
-        if !url_string.ends_with('/') {
-            url_string.push('/');
-        }
-        url_string.push_str(endpoint);
-        let url = url_string.parse::<url::Url>().map_err(|e| Error::Config {
-            message: format!("Invalid URL: {url_string}"),
-            source: Some(Box::new(e)),
-        })?;
+        let url = self.endpoint_url(endpoint)?;
 
         debug!("📥 GET request: {}", url);
 
@@ -123,30 +434,66 @@ impl Client {
 
     /// Handle HTTP response and parse JSON
     async fn handle_response(&self, response: reqwest::Response) -> Result<Value> {
-        let status = response.status();
-        let status_code = status.as_u16();
-
-        if status.is_success() {
+        if response.status().is_success() {
             let text = response.text().await?;
-            debug!("✅ Response: {}", text);
+            debug!("✅ Response: {}", redact::text(&text));
 
             serde_json::from_str(&text).map_err(|e| Error::Json {
                 context: format!("Failed to parse API response: {text}"),
                 source: e,
             })
         } else {
-            let error_text = response.text().await.unwrap_or_default();
-            debug!("❌ Error response: {} - {}", status_code, error_text);
+            Err(Self::error_from_response(response).await)
+        }
+    }
 
-            Err(Error::Api {
-                status: status_code,
-                message: format!("API request failed with status {status_code}"),
-                body: Some(error_text),
-            })
+    /// Convert unsuccessful HTTP response into API error
+    async fn error_from_response(response: reqwest::Response) -> Error {
+        let status_code = response.status().as_u16();
+        let error_text = response.text().await.unwrap_or_default();
+        debug!(
+            "❌ Error response: {} - {}",
+            status_code,
+            redact::text(&error_text)
+        );
+
+        Error::Api {
+            status: status_code,
+            message: format!("API request failed with status {status_code}"),
+            body: Some(error_text),
         }
     }
 }
 
+/// Send a request to Outline in its own span, recording status and latency
+/// in the metrics and forwarding the trace context
+async fn send_upstream(
+    endpoint: &str,
+    request: reqwest::RequestBuilder,
+) -> std::result::Result<reqwest::Response, reqwest::Error> {
+    let span = info_span!("outline", endpoint, status = field::Empty);
+    async {
+        let started = Instant::now();
+        let response = telemetry::inject(request).send().await;
+        let status = match &response {
+            Ok(response) => response.status().as_u16().to_string(),
+            Err(e) if e.is_timeout() => "timeout".to_string(),
+            Err(_) => "network_error".to_string(),
+        };
+        Span::current().record("status", status.as_str());
+        crate::metrics::global().observe_outline(endpoint, &status, started.elapsed());
+        response
+    }
+    .instrument(span)
+    .await
+}
+
+/// Read a PEM file named in the transport settings
+fn read_pem(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path)
+        .map_err(|e| Error::config_with_source(format!("Cannot read {}", path.display()), e))
+}
+
 // Helper functions for API operations
 
 /// Create document request body
@@ -206,18 +553,47 @@ pub fn create_collection_request(name: &str, description: Option<&str>) -> Value
     request
 }
 
-/// Create comment request body
-pub fn create_comment_request(document_id: &str, data: &str) -> Value {
-    serde_json::json!({
+/// Create comment request body with markdown text
+pub fn create_comment_request(
+    document_id: &str,
+    text: &str,
+    parent_comment_id: Option<&str>,
+) -> Value {
+    let mut request = serde_json::json!({
         "documentId": document_id,
-        "data": data
-    })
+        "text": text
+    });
+
+    if let Some(parent) = parent_comment_id {
+        request["parentCommentId"] = serde_json::json!(parent);
+    }
+
+    request
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_build_http_client_transport() {
+        assert!(Client::build_http_client(&TransportConfig::default()).is_ok());
+
+        let insecure = TransportConfig {
+            proxy: Some("http://proxy.internal:3128".parse().unwrap()),
+            insecure_skip_verify: true,
+            ..TransportConfig::default()
+        };
+        assert!(Client::build_http_client(&insecure).is_ok());
+
+        let missing_ca = TransportConfig {
+            ca_certs: vec!["/nonexistent/ca.pem".into()],
+            ..TransportConfig::default()
+        };
+        let err = Client::build_http_client(&missing_ca).unwrap_err();
+        assert!(err.to_string().contains("/nonexistent/ca.pem"));
+    }
+
     #[test]
     fn test_create_document_request() {
         let request = create_document_request("Test Title", "Test content", Some("collection-id"));
@@ -232,4 +608,15 @@ mod tests {
         assert_eq!(request["query"], "test query");
         assert_eq!(request["limit"], 10);
     }
+
+    #[test]
+    fn test_create_comment_request() {
+        let request = create_comment_request("doc-id", "**Looks good**", Some("parent-id"));
+        assert_eq!(request["documentId"], "doc-id");
+        assert_eq!(request["text"], "**Looks good**");
+        assert_eq!(request["parentCommentId"], "parent-id");
+
+        let request = create_comment_request("doc-id", "Top level", None);
+        assert!(request.get("parentCommentId").is_none());
+    }
 }
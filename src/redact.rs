@@ -0,0 +1,138 @@
+//! Redaction for debug logs
+//!
+//! Request and response bodies are logged at debug level to trace problems,
+//! but they carry document content and credentials. These helpers keep the
+//! structure of a payload and replace what must not leave the process.
+
+use serde_json::Value;
+
+/// Keys holding document or message bodies, replaced by their length
+const BODY_KEYS: &[&str] = &[
+    "text",
+    "markdown",
+    "content",
+    "content_base64",
+    "data",
+    "blob",
+    "context",
+    "answer",
+    "snippet",
+];
+
+/// Keys holding credentials, replaced entirely
+const SECRET_KEYS: &[&str] = &[
+    "authorization",
+    "token",
+    "apikey",
+    "api_key",
+    "password",
+    "secret",
+];
+
+/// Headers that are never logged
+const SECRET_HEADERS: &[&str] = &["authorization", "x-mcp-token", "cookie"];
+
+/// Render `value` for a log line with bodies and secrets redacted
+pub fn json(value: &Value) -> String {
+    let mut value = value.clone();
+    redact_value(&mut value);
+    value.to_string()
+}
+
+/// Render a raw payload for a log line, redacting it as JSON when it parses
+pub fn text(raw: &str) -> String {
+    serde_json::from_str::<Value>(raw).map_or_else(
+        |_| format!("[{} bytes redacted]", raw.len()),
+        |value| json(&value),
+    )
+}
+
+/// Render request headers for a log line without credentials
+pub fn headers(headers: &hyper::HeaderMap) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let shown = if SECRET_HEADERS.contains(&name.as_str()) {
+                "[redacted]"
+            } else {
+                value.to_str().unwrap_or("[binary]")
+            };
+            format!("{name}: {shown}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                let key = key.to_lowercase();
+                if SECRET_KEYS.contains(&key.as_str()) {
+                    *item = Value::String("[redacted]".to_string());
+                } else if BODY_KEYS.contains(&key.as_str()) && item.is_string() {
+                    let chars = item.as_str().map_or(0, |s| s.chars().count());
+                    *item = Value::String(format!("[{chars} chars redacted]"));
+                } else {
+                    redact_value(item);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redacts_bodies_and_secrets() {
+        let value = json!({
+            "params": {
+                "name": "update_document",
+                "arguments": { "id": "doc-1", "text": "Quarterly numbers", "apiKey": "ol_api_x" }
+            },
+            "result": { "content": [{ "type": "text", "text": "secret plans" }] }
+        });
+
+        let logged = json(&value);
+        assert!(logged.contains("doc-1"));
+        assert!(logged.contains("update_document"));
+        assert!(logged.contains("[17 chars redacted]"));
+        assert!(!logged.contains("Quarterly"));
+        assert!(!logged.contains("secret plans"));
+        assert!(!logged.contains("ol_api_x"));
+    }
+
+    #[test]
+    fn test_redacts_search_answers_and_imports() {
+        let value = json!({
+            "data": [{ "context": "the <b>launch</b> date", "ranking": 0.5 }],
+            "search": { "answer": "It launches in May", "snippet": "May launch" },
+            "arguments": { "content_base64": "IyBQbGFu", "file_name": "plan.md" }
+        });
+
+        let logged = json(&value);
+        assert!(!logged.contains("launch"));
+        assert!(!logged.contains("May"));
+        assert!(!logged.contains("IyBQbGFu"));
+        assert!(logged.contains("plan.md"));
+        assert!(logged.contains("0.5"));
+    }
+
+    #[test]
+    fn test_redacts_raw_text_and_headers() {
+        assert_eq!(text("not json at all"), "[15 bytes redacted]");
+        assert!(text(r#"{"data":{"text":"body"}}"#).contains("[4 chars redacted]"));
+
+        let mut map = hyper::HeaderMap::new();
+        map.insert("authorization", "Bearer ol_api_x".parse().unwrap());
+        map.insert("content-type", "application/json".parse().unwrap());
+        let logged = headers(&map);
+        assert!(logged.contains("authorization: [redacted]"));
+        assert!(logged.contains("content-type: application/json"));
+    }
+}
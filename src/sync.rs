@@ -0,0 +1,535 @@
+//! Local directory sync
+//!
+//! Mirrors an Outline collection into a folder of markdown files with YAML
+//! front-matter (`id`, `title`, `updatedAt`, `parent`) and pushes local edits
+//! back with `documents.update`.
+//!
+//! The `updatedAt` value in each file's front-matter is the remote version the
+//! local copy is based on. A hash of the last synced content is kept in
+//! `.outline-sync.json` to detect local edits. When both sides changed, the
+//! remote version is written next to the file as `<file>.conflict` and the
+//! local file is left untouched; to resolve, merge the changes, copy
+//! `updatedAt` from the conflict file and delete it.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::error::{Error, Result};
+use crate::outline::Client as OutlineClient;
+
+/// Name of the sync state file kept in the target directory
+const STATE_FILE: &str = ".outline-sync.json";
+
+/// Page size for `documents.list`
+const PAGE_SIZE: usize = 100;
+
+/// Sync parameters
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    /// Collection to mirror
+    pub collection_id: String,
+    /// Local directory holding the markdown files
+    pub directory: PathBuf,
+    /// Report what would happen without writing files or pushing changes
+    pub dry_run: bool,
+}
+
+/// Outcome of syncing a single file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    /// New remote document written locally
+    Created,
+    /// Remote changes written over an unmodified local file
+    Pulled,
+    /// Local edits pushed to Outline
+    Pushed,
+    /// Both sides changed, remote version written to a `.conflict` file
+    Conflict,
+    /// Nothing to do
+    Unchanged,
+    /// Document no longer exists in the collection, local file kept
+    DeletedRemotely,
+    /// Markdown file without front-matter `id`, ignored
+    Untracked,
+}
+
+impl SyncAction {
+    /// Get action name for reports
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Pulled => "pulled",
+            Self::Pushed => "pushed",
+            Self::Conflict => "conflict",
+            Self::Unchanged => "unchanged",
+            Self::DeletedRemotely => "deleted_remotely",
+            Self::Untracked => "untracked",
+        }
+    }
+}
+
+/// Sync result for a single file
+#[derive(Debug, Clone)]
+pub struct SyncEntry {
+    /// File name relative to the sync directory
+    pub file: String,
+    /// Document ID (if known)
+    pub id: Option<String>,
+    /// What happened to the file
+    pub action: SyncAction,
+}
+
+impl SyncEntry {
+    /// Convert entry to JSON for tool responses
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        json!({
+            "file": self.file,
+            "id": self.id,
+            "action": self.action.as_str()
+        })
+    }
+}
+
+/// Document parsed from a local markdown file
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LocalDocument {
+    id: Option<String>,
+    title: String,
+    updated_at: String,
+    parent: Option<String>,
+    body: String,
+}
+
+/// Synchronize a collection with a local directory
+///
+/// # Errors
+///
+/// Returns error if the directory cannot be read or written, or if an Outline
+/// API call fails.
+pub async fn sync_collection(
+    client: &OutlineClient,
+    options: &SyncOptions,
+) -> Result<Vec<SyncEntry>> {
+    let dir = &options.directory;
+    if !options.dry_run {
+        fs::create_dir_all(dir)
+            .map_err(|e| Error::io(format!("Failed to create {}", dir.display()), e))?;
+    }
+
+    let remote = fetch_collection_documents(client, &options.collection_id).await?;
+    let local = scan_directory(dir)?;
+    let mut hashes = load_state(dir);
+
+    let mut entries = Vec::new();
+    let mut used_names: HashSet<String> = local.keys().cloned().collect();
+    let mut local_by_id: HashMap<String, (String, LocalDocument)> = HashMap::new();
+
+    for (file, document) in local {
+        match document.id.clone() {
+            Some(id) => {
+                local_by_id.insert(id, (file, document));
+            }
+            None => entries.push(SyncEntry {
+                file,
+                id: None,
+                action: SyncAction::Untracked,
+            }),
+        }
+    }
+
+    for document in &remote {
+        let Some(id) = document["id"].as_str() else {
+            continue;
+        };
+        let remote_doc = LocalDocument::from_remote(document);
+
+        let Some((file, local_doc)) = local_by_id.remove(id) else {
+            let file = unique_file_name(&remote_doc.title, id, &used_names);
+            used_names.insert(file.clone());
+            if !options.dry_run {
+                write_file(&dir.join(&file), &remote_doc.render())?;
+            }
+            hashes.insert(id.to_string(), remote_doc.content_hash());
+            entries.push(SyncEntry {
+                file,
+                id: Some(id.to_string()),
+                action: SyncAction::Created,
+            });
+            continue;
+        };
+
+        let action =
+            reconcile(client, options, &file, &local_doc, &remote_doc, &mut hashes).await?;
+
+        debug!("Sync {}: {}", file, action.as_str());
+        entries.push(SyncEntry {
+            file,
+            id: Some(id.to_string()),
+            action,
+        });
+    }
+
+    for (id, (file, _)) in local_by_id {
+        hashes.remove(&id);
+        entries.push(SyncEntry {
+            file,
+            id: Some(id),
+            action: SyncAction::DeletedRemotely,
+        });
+    }
+
+    if !options.dry_run {
+        save_state(dir, &options.collection_id, &hashes)?;
+    }
+
+    entries.sort_by(|a, b| a.file.cmp(&b.file));
+    Ok(entries)
+}
+
+/// Decide and apply the sync action for a document present on both sides
+async fn reconcile(
+    client: &OutlineClient,
+    options: &SyncOptions,
+    file: &str,
+    local_doc: &LocalDocument,
+    remote_doc: &LocalDocument,
+    hashes: &mut HashMap<String, String>,
+) -> Result<SyncAction> {
+    let dir = &options.directory;
+    let id = remote_doc.id.clone().unwrap_or_default();
+
+    let remote_changed = local_doc.updated_at != remote_doc.updated_at;
+    let local_changed = hashes.get(id.as_str()).map_or_else(
+        || !local_doc.same_content(remote_doc),
+        |hash| *hash != local_doc.content_hash(),
+    );
+
+    Ok(match (local_changed, remote_changed) {
+        (false, false) => SyncAction::Unchanged,
+        (false, true) => {
+            if !options.dry_run {
+                write_file(&dir.join(file), &remote_doc.render())?;
+            }
+            hashes.insert(id.clone(), remote_doc.content_hash());
+            SyncAction::Pulled
+        }
+        (true, false) => {
+            if !options.dry_run {
+                let response = client
+                    .post(
+                        "documents.update",
+                        json!({
+                            "id": id,
+                            "title": local_doc.title,
+                            "text": local_doc.body
+                        }),
+                    )
+                    .await?;
+                let pushed = LocalDocument {
+                    updated_at: response["data"]["updatedAt"]
+                        .as_str()
+                        .unwrap_or(&local_doc.updated_at)
+                        .to_string(),
+                    ..local_doc.clone()
+                };
+                write_file(&dir.join(file), &pushed.render())?;
+            }
+            hashes.insert(id.clone(), local_doc.content_hash());
+            SyncAction::Pushed
+        }
+        (true, true) if local_doc.same_content(remote_doc) => {
+            // Same edit on both sides, only the base version moves forward
+            if !options.dry_run {
+                write_file(&dir.join(file), &remote_doc.render())?;
+            }
+            hashes.insert(id.clone(), remote_doc.content_hash());
+            SyncAction::Unchanged
+        }
+        (true, true) => {
+            if !options.dry_run {
+                write_file(&dir.join(format!("{file}.conflict")), &remote_doc.render())?;
+            }
+            SyncAction::Conflict
+        }
+    })
+}
+
+/// Fetch all documents of a collection, following pagination
+async fn fetch_collection_documents(
+    client: &OutlineClient,
+    collection_id: &str,
+) -> Result<Vec<Value>> {
+    let mut documents = Vec::new();
+
+    loop {
+        let response = client
+            .post(
+                "documents.list",
+                json!({
+                    "collectionId": collection_id,
+                    "limit": PAGE_SIZE,
+                    "offset": documents.len()
+                }),
+            )
+            .await?;
+        let page = response["data"].as_array().cloned().unwrap_or_default();
+        let page_len = page.len();
+        documents.extend(page);
+
+        if page_len < PAGE_SIZE {
+            break;
+        }
+    }
+
+    Ok(documents)
+}
+
+/// Read all markdown files of the sync directory keyed by file name
+fn scan_directory(dir: &Path) -> Result<HashMap<String, LocalDocument>> {
+    let mut documents = HashMap::new();
+    if !dir.exists() {
+        return Ok(documents);
+    }
+
+    let entries =
+        fs::read_dir(dir).map_err(|e| Error::io(format!("Failed to read {}", dir.display()), e))?;
+
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+        let Some(file) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| Error::io(format!("Failed to read {}", path.display()), e))?;
+        let document = LocalDocument::parse(&content).unwrap_or_else(|| LocalDocument {
+            id: None,
+            title: String::new(),
+            updated_at: String::new(),
+            parent: None,
+            body: content,
+        });
+        documents.insert(file.to_string(), document);
+    }
+
+    Ok(documents)
+}
+
+/// Load content hashes from the state file (missing or invalid state is empty)
+fn load_state(dir: &Path) -> HashMap<String, String> {
+    fs::read_to_string(dir.join(STATE_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .and_then(|state| {
+            state["hashes"].as_object().map(|map| {
+                map.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|h| (k.clone(), h.to_string())))
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Persist content hashes to the state file
+fn save_state(dir: &Path, collection_id: &str, hashes: &HashMap<String, String>) -> Result<()> {
+    // Sorted keys keep the state file stable for version control
+    let sorted: std::collections::BTreeMap<_, _> = hashes.iter().collect();
+    let state = json!({
+        "collectionId": collection_id,
+        "hashes": sorted
+    });
+    write_file(
+        &dir.join(STATE_FILE),
+        &format!("{}\n", serde_json::to_string_pretty(&state)?),
+    )
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    fs::write(path, content)
+        .map_err(|e| Error::io(format!("Failed to write {}", path.display()), e))
+}
+
+/// Build a file name from the document title that is not used yet
+fn unique_file_name(title: &str, id: &str, used: &HashSet<String>) -> String {
+    let stem: String = title
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let stem = stem.trim().trim_matches('-');
+    let stem = if stem.is_empty() { "untitled" } else { stem };
+
+    let name = format!("{stem}.md");
+    if used.contains(&name) {
+        let short_id: String = id.chars().take(8).collect();
+        format!("{stem}-{short_id}.md")
+    } else {
+        name
+    }
+}
+
+impl LocalDocument {
+    fn from_remote(document: &Value) -> Self {
+        Self {
+            id: document["id"].as_str().map(ToString::to_string),
+            title: document["title"].as_str().unwrap_or_default().to_string(),
+            updated_at: document["updatedAt"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            parent: document["parentDocumentId"]
+                .as_str()
+                .map(ToString::to_string),
+            body: document["text"].as_str().unwrap_or_default().to_string(),
+        }
+    }
+
+    /// Parse a markdown file with YAML front-matter
+    fn parse(content: &str) -> Option<Self> {
+        let content = content.replace("\r\n", "\n");
+        let rest = content.strip_prefix("---\n")?;
+        let end = rest.find("\n---\n")?;
+        let (header, body) = (&rest[..end], &rest[end + "\n---\n".len()..]);
+
+        let mut document = Self {
+            id: None,
+            title: String::new(),
+            updated_at: String::new(),
+            parent: None,
+            body: body.to_string(),
+        };
+
+        for line in header.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = parse_yaml_scalar(value.trim());
+            match key.trim() {
+                "id" if !value.is_empty() => document.id = Some(value),
+                "title" => document.title = value,
+                "updatedAt" => document.updated_at = value,
+                "parent" if !value.is_empty() => document.parent = Some(value),
+                _ => {}
+            }
+        }
+
+        Some(document)
+    }
+
+    /// Render the document as markdown with YAML front-matter
+    fn render(&self) -> String {
+        // JSON strings are valid YAML double-quoted scalars
+        let quote = |s: &str| serde_json::to_string(s).unwrap_or_else(|_| "\"\"".to_string());
+        let parent = self
+            .parent
+            .as_deref()
+            .map_or_else(String::new, |p| format!(" {}", quote(p)));
+        format!(
+            "---\nid: {}\ntitle: {}\nupdatedAt: {}\nparent:{}\n---\n{}",
+            self.id.as_deref().unwrap_or_default(),
+            quote(&self.title),
+            quote(&self.updated_at),
+            parent,
+            self.body
+        )
+    }
+
+    fn same_content(&self, other: &Self) -> bool {
+        self.title == other.title && self.body == other.body
+    }
+
+    /// Stable FNV-1a hash of title and body
+    fn content_hash(&self) -> String {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in self
+            .title
+            .bytes()
+            .chain(std::iter::once(0))
+            .chain(self.body.bytes())
+        {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        format!("{hash:016x}")
+    }
+}
+
+/// Parse a single-line YAML scalar (plain, single- or double-quoted)
+fn parse_yaml_scalar(value: &str) -> String {
+    if value.starts_with('"') {
+        serde_json::from_str(value).unwrap_or_else(|_| value.trim_matches('"').to_string())
+    } else if value.len() >= 2 && value.starts_with('\'') && value.ends_with('\'') {
+        value[1..value.len() - 1].replace("''", "'")
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> LocalDocument {
+        LocalDocument {
+            id: Some("doc-1".to_string()),
+            title: "Runbook: \"deploy\"".to_string(),
+            updated_at: "2024-01-01T00:00:00.000Z".to_string(),
+            parent: Some("doc-0".to_string()),
+            body: "# Deploy\n\n---\n\nSteps\n".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_parse_roundtrip() {
+        let document = sample();
+        let rendered = document.render();
+        assert!(rendered.starts_with("---\nid: doc-1\n"));
+        assert_eq!(LocalDocument::parse(&rendered), Some(document));
+    }
+
+    #[test]
+    fn test_parse_without_front_matter() {
+        assert!(LocalDocument::parse("# Just markdown\n").is_none());
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        let document = sample();
+        assert_eq!(document.content_hash(), sample().content_hash());
+
+        let edited = LocalDocument {
+            body: "changed".to_string(),
+            ..sample()
+        };
+        assert_ne!(document.content_hash(), edited.content_hash());
+    }
+
+    #[test]
+    fn test_unique_file_name() {
+        let mut used = HashSet::new();
+        assert_eq!(
+            unique_file_name("Deploy / Rollback", "abcdef123", &used),
+            "Deploy - Rollback.md"
+        );
+        used.insert("Deploy.md".to_string());
+        assert_eq!(
+            unique_file_name("Deploy", "abcdef123", &used),
+            "Deploy-abcdef12.md"
+        );
+        assert_eq!(unique_file_name("???", "x", &used), "untitled.md");
+    }
+}
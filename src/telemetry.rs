@@ -0,0 +1,367 @@
+//! OpenTelemetry trace export
+//!
+//! Built with the `otel` cargo feature and with `OTEL_EXPORTER_OTLP_ENDPOINT`
+//! set, the spans of HTTP requests, JSON-RPC dispatch, tool calls and Outline
+//! requests are exported over OTLP. The W3C `traceparent` of an incoming
+//! request becomes the parent of its span and is forwarded to Outline, so the
+//! server shows up inside the caller's trace. Without the feature everything
+//! here is a no-op and the default binary carries no OpenTelemetry code.
+
+use tracing::Subscriber;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::error::Result;
+
+/// Trace exporter; dropping it flushes the spans not yet exported
+#[derive(Debug, Default)]
+pub struct Telemetry {
+    #[cfg(feature = "otel")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Telemetry {
+    /// Set up export from the standard `OTEL_*` environment variables
+    ///
+    /// Export is enabled by `OTEL_EXPORTER_OTLP_ENDPOINT` (or
+    /// `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`). `OTEL_EXPORTER_OTLP_PROTOCOL`
+    /// selects `grpc` or `http/protobuf` (default), and `OTEL_SERVICE_NAME`
+    /// overrides the service name `outline-mcp`. Must be called within the
+    /// Tokio runtime, which the gRPC exporter uses.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the exporter cannot be built, e.g. for an unknown protocol.
+    #[cfg(feature = "otel")]
+    pub fn from_env() -> Result<Self> {
+        let configured = [
+            "OTEL_EXPORTER_OTLP_ENDPOINT",
+            "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+        ]
+        .iter()
+        .any(|name| std::env::var(name).is_ok_and(|value| !value.trim().is_empty()));
+        if !configured {
+            return Ok(Self::default());
+        }
+
+        Ok(Self::with_exporter(otlp::exporter()?))
+    }
+
+    /// Export spans through `exporter` and propagate W3C trace context
+    #[cfg(feature = "otel")]
+    fn with_exporter(exporter: opentelemetry_otlp::SpanExporter) -> Self {
+        use opentelemetry::global;
+        use opentelemetry_sdk::propagation::TraceContextPropagator;
+        use opentelemetry_sdk::trace::SdkTracerProvider;
+        use opentelemetry_sdk::Resource;
+
+        let mut resource = Resource::builder();
+        if std::env::var("OTEL_SERVICE_NAME").is_err() {
+            resource = resource.with_service_name(SERVICE_NAME);
+        }
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(resource.build())
+            .build();
+
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        Self {
+            provider: Some(provider),
+        }
+    }
+
+    /// Without the `otel` feature there is nothing to set up
+    ///
+    /// # Errors
+    ///
+    /// Never returns an error.
+    #[cfg(not(feature = "otel"))]
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::default())
+    }
+
+    /// Whether spans are exported
+    #[must_use]
+    pub const fn enabled(&self) -> bool {
+        #[cfg(feature = "otel")]
+        return self.provider.is_some();
+        #[cfg(not(feature = "otel"))]
+        false
+    }
+
+    /// Layer turning this crate's spans into exported OpenTelemetry spans
+    ///
+    /// The layer has its own filter: spans are exported at `info` level
+    /// regardless of `RUST_LOG`, and spans of the exporter's own HTTP and gRPC
+    /// clients are left out.
+    #[cfg(feature = "otel")]
+    #[must_use]
+    pub fn layer<S>(&self) -> Option<Box<dyn Layer<S> + Send + Sync>>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a> + Send + Sync,
+    {
+        use opentelemetry::trace::TracerProvider as _;
+        use tracing_subscriber::filter::Targets;
+
+        let tracer = self.provider.as_ref()?.tracer(SERVICE_NAME);
+        let targets = Targets::new().with_target("outline_mcp_rs", tracing::Level::INFO);
+        Some(
+            tracing_opentelemetry::layer()
+                .with_tracer(tracer)
+                .with_filter(targets)
+                .boxed(),
+        )
+    }
+
+    /// Without the `otel` feature no layer is added
+    #[cfg(not(feature = "otel"))]
+    #[must_use]
+    pub fn layer<S>(&self) -> Option<Box<dyn Layer<S> + Send + Sync>>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a> + Send + Sync,
+    {
+        None
+    }
+}
+
+#[cfg(feature = "otel")]
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                tracing::warn!("Failed to flush OpenTelemetry spans: {e}");
+            }
+        }
+    }
+}
+
+/// Service name reported when `OTEL_SERVICE_NAME` is not set
+#[cfg(feature = "otel")]
+const SERVICE_NAME: &str = "outline-mcp";
+
+/// Make the `traceparent` of incoming request `headers` the parent of `span`
+///
+/// Must be called before `span` is first entered.
+#[cfg(feature = "otel")]
+pub(crate) fn continue_trace(span: &tracing::Span, headers: &hyper::HeaderMap) {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&propagation::Headers(headers))
+    });
+    // Fails only when no OpenTelemetry layer is installed
+    let _ = span.set_parent(parent);
+}
+
+#[cfg(not(feature = "otel"))]
+pub(crate) const fn continue_trace(_span: &tracing::Span, _headers: &hyper::HeaderMap) {}
+
+/// Add the `traceparent` of the current span to a request sent to Outline
+#[cfg(feature = "otel")]
+pub(crate) fn inject(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let context = tracing::Span::current().context();
+    let mut headers = propagation::Fields::default();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut headers);
+    });
+    headers
+        .0
+        .into_iter()
+        .fold(request, |request, (name, value)| {
+            request.header(name, value)
+        })
+}
+
+#[cfg(not(feature = "otel"))]
+pub(crate) const fn inject(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    request
+}
+
+#[cfg(feature = "otel")]
+mod otlp {
+    use opentelemetry_otlp::{Protocol, SpanExporter, WithExportConfig};
+
+    use crate::error::{Error, Result};
+
+    /// Build the span exporter for `OTEL_EXPORTER_OTLP_PROTOCOL`
+    pub fn exporter() -> Result<SpanExporter> {
+        let protocol = std::env::var("OTEL_EXPORTER_OTLP_PROTOCOL")
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        let exporter = match protocol.as_str() {
+            "grpc" => SpanExporter::builder().with_tonic().build(),
+            "" | "http/protobuf" => SpanExporter::builder()
+                .with_http()
+                .with_protocol(Protocol::HttpBinary)
+                .build(),
+            other => {
+                return Err(Error::Config {
+                    message: format!(
+                        "Unsupported OTEL_EXPORTER_OTLP_PROTOCOL '{other}' (expected grpc or http/protobuf)"
+                    ),
+                    source: None,
+                })
+            }
+        };
+        exporter.map_err(|e| Error::config_with_source("Failed to create OTLP span exporter", e))
+    }
+}
+
+#[cfg(feature = "otel")]
+mod propagation {
+    use std::collections::HashMap;
+
+    use opentelemetry::propagation::{Extractor, Injector};
+
+    /// Incoming request headers as a propagation carrier
+    pub struct Headers<'a>(pub &'a hyper::HeaderMap);
+
+    impl Extractor for Headers<'_> {
+        fn get(&self, key: &str) -> Option<&str> {
+            self.0.get(key).and_then(|value| value.to_str().ok())
+        }
+
+        fn keys(&self) -> Vec<&str> {
+            self.0
+                .keys()
+                .map(hyper::header::HeaderName::as_str)
+                .collect()
+        }
+    }
+
+    /// Fields to add to an outgoing request
+    #[derive(Default)]
+    pub struct Fields(pub HashMap<String, String>);
+
+    impl Injector for Fields {
+        fn set(&mut self, key: &str, value: String) {
+            self.0.insert(key.to_string(), value);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "otel"))]
+mod tests {
+    use super::*;
+    use opentelemetry_otlp::{Protocol, SpanExporter, WithExportConfig};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use tracing_subscriber::prelude::*;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+    /// OTLP/HTTP collector stand-in sending back each request line and body
+    fn collector() -> (String, mpsc::Receiver<(String, Vec<u8>)>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { break };
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let _ = stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n");
+                let _ = tx.send((request_line.trim().to_string(), body));
+            }
+        });
+        (format!("http://{addr}/v1/traces"), rx)
+    }
+
+    fn telemetry(endpoint: &str) -> Telemetry {
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_protocol(Protocol::HttpBinary)
+            .with_endpoint(endpoint)
+            .build()
+            .unwrap();
+        Telemetry::with_exporter(exporter)
+    }
+
+    #[test]
+    fn test_spans_exported_to_collector() {
+        let (endpoint, requests) = collector();
+        let telemetry = telemetry(&endpoint);
+        let subscriber = tracing_subscriber::registry().with(telemetry.layer());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("http", request_id = "req-1");
+            let mut headers = hyper::HeaderMap::new();
+            headers.insert(
+                "traceparent",
+                format!("00-{TRACE_ID}-00f067aa0ba902b7-01")
+                    .parse()
+                    .unwrap(),
+            );
+            continue_trace(&span, &headers);
+            span.in_scope(|| {
+                tracing::info_span!("tool", tool = "get_document").in_scope(|| {});
+            });
+        });
+        // Shutting down flushes the batch
+        drop(telemetry);
+
+        let (request_line, body) = requests.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(request_line, "POST /v1/traces HTTP/1.1");
+        let contains = |needle: &[u8]| body.windows(needle.len()).any(|w| w == needle);
+        assert!(contains(b"get_document"));
+        assert!(contains(b"outline-mcp"));
+        // Trace IDs are sent as raw bytes
+        let trace_id: Vec<u8> = (0..TRACE_ID.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&TRACE_ID[i..i + 2], 16).unwrap())
+            .collect();
+        assert!(contains(&trace_id));
+    }
+
+    #[test]
+    fn test_traceparent_forwarded_to_outline() {
+        let (endpoint, _requests) = collector();
+        let telemetry = telemetry(&endpoint);
+        let subscriber = tracing_subscriber::registry().with(telemetry.layer());
+
+        let traceparent = tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("http");
+            let mut headers = hyper::HeaderMap::new();
+            headers.insert(
+                "traceparent",
+                format!("00-{TRACE_ID}-00f067aa0ba902b7-01")
+                    .parse()
+                    .unwrap(),
+            );
+            continue_trace(&span, &headers);
+            span.in_scope(|| {
+                let request = inject(reqwest::Client::new().post("http://outline.invalid/api"));
+                request.build().unwrap().headers()["traceparent"]
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+            })
+        });
+
+        let parts: Vec<&str> = traceparent.split('-').collect();
+        assert_eq!(parts[1], TRACE_ID);
+        // Outline sees our span as its parent, not the caller's
+        assert_ne!(parts[2], "00f067aa0ba902b7");
+        assert_eq!(parts[3], "01");
+    }
+}
@@ -0,0 +1,316 @@
+//! Content analytics tools
+//!
+//! View statistics plus stale and orphaned document reports built on
+//! `views.list`, `documents.list` and the collection navigation tree.
+
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use serde_json::{json, Value};
+use tracing::debug;
+
+use super::common::{
+    create_mcp_success_response, get_optional_number_arg, get_string_arg, iso_timestamp,
+    tool_definition, unix_now, with_optional_params,
+};
+use crate::error::{Error, Result};
+use crate::outline::Client as OutlineClient;
+
+/// Default age in days after which a document counts as stale
+const DEFAULT_STALE_DAYS: u64 = 90;
+
+/// Default number of documents in a report
+const DEFAULT_REPORT_LIMIT: usize = 20;
+
+/// Page size used when walking a collection's documents
+const DOCUMENTS_PAGE_SIZE: usize = 100;
+
+/// Upper bound on documents inspected by one report
+const MAX_REPORT_CANDIDATES: usize = 200;
+
+/// Concurrent `views.list` requests while building a report
+const VIEWS_CONCURRENCY: usize = 4;
+
+/// Get all analytics tool definitions
+pub fn get_analytics_tools() -> Vec<Value> {
+    vec![
+        tool_definition(
+            "get_document_views",
+            "Get view counts of a document per user, with totals",
+            &[("id", "string", "Document ID")],
+        ),
+        with_optional_params(tool_definition(
+            "find_stale_documents",
+            "List documents in a collection not updated for N days, most viewed first among the 200 oldest",
+            &[("collection_id", "string", "Collection ID")]), &[(
+                    "days",
+                    "number",
+                    "Days without updates, default 90 (optional)",
+                ), (
+                    "limit",
+                    "number",
+                    "Number of documents to return, default 20 (optional)",
+                )]),
+        tool_definition(
+            "find_orphan_documents",
+            "List top-level documents of a collection that no other document links to",
+            &[("collection_id", "string", "Collection ID")],
+        ),
+    ]
+}
+
+/// Call analytics tool
+pub async fn call_analytics_tool(
+    name: &str,
+    arguments: Value,
+    client: &OutlineClient,
+) -> Result<Value> {
+    match name {
+        "get_document_views" => get_document_views(arguments, client).await,
+        "find_stale_documents" => find_stale_documents(arguments, client).await,
+        "find_orphan_documents" => find_orphan_documents(arguments, client).await,
+        _ => unreachable!("Unknown analytics tool: {}", name),
+    }
+}
+
+async fn get_document_views(args: Value, client: &OutlineClient) -> Result<Value> {
+    let id = get_string_arg(&args, "id")?;
+
+    debug!("Getting views for document: {}", id);
+
+    let mut response = client
+        .post("views.list", json!({ "documentId": id }))
+        .await?;
+    let summary = summarize_views(response["data"].as_array().map_or(&[], Vec::as_slice));
+    response["summary"] = summary;
+
+    Ok(create_mcp_success_response(
+        "Document views retrieved successfully",
+        Some(response),
+    ))
+}
+
+async fn find_stale_documents(args: Value, client: &OutlineClient) -> Result<Value> {
+    let collection_id = get_string_arg(&args, "collection_id")?;
+    let days = get_optional_number_arg(&args, "days")
+        .and_then(|d| u64::try_from(d).ok())
+        .unwrap_or(DEFAULT_STALE_DAYS);
+    let limit = get_optional_number_arg(&args, "limit")
+        .and_then(|l| usize::try_from(l).ok())
+        .unwrap_or(DEFAULT_REPORT_LIMIT);
+    let cutoff = iso_timestamp(unix_now().saturating_sub(days.saturating_mul(86_400)));
+
+    debug!(
+        "Finding documents in {} not updated since {}",
+        collection_id, cutoff
+    );
+
+    // Oldest first, so paging can stop at the first fresh document. Only the
+    // oldest MAX_REPORT_CANDIDATES stale documents are ranked.
+    let mut stale = Vec::new();
+    let mut offset = 0;
+    let mut truncated = false;
+    'pages: loop {
+        let response = client
+            .post(
+                "documents.list",
+                json!({
+                    "collectionId": collection_id,
+                    "sort": "updatedAt",
+                    "direction": "ASC",
+                    "limit": DOCUMENTS_PAGE_SIZE,
+                    "offset": offset
+                }),
+            )
+            .await?;
+        let page = response["data"].as_array().cloned().unwrap_or_default();
+        offset += page.len();
+
+        for document in &page {
+            if document["updatedAt"].as_str().unwrap_or_default() >= cutoff.as_str() {
+                break 'pages;
+            }
+            if stale.len() == MAX_REPORT_CANDIDATES {
+                truncated = true;
+                break 'pages;
+            }
+            stale.push(document.clone());
+        }
+        if page.len() < DOCUMENTS_PAGE_SIZE {
+            break;
+        }
+    }
+
+    let mut report: Vec<Value> = stream::iter(stale)
+        .map(|document| async move {
+            let id = document["id"].as_str().unwrap_or_default();
+            let views = client
+                .post("views.list", json!({ "documentId": id }))
+                .await?;
+            let summary = summarize_views(views["data"].as_array().map_or(&[], Vec::as_slice));
+
+            Ok::<_, Error>(json!({
+                "id": id,
+                "title": document["title"],
+                "url": document["url"],
+                "updatedAt": document["updatedAt"],
+                "updatedBy": document["updatedBy"]["name"],
+                "views": summary["views"],
+                "viewers": summary["viewers"],
+                "lastViewedAt": summary["lastViewedAt"]
+            }))
+        })
+        .buffer_unordered(VIEWS_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+    rank_by_views(&mut report);
+    let total = report.len();
+    report.truncate(limit);
+
+    Ok(create_mcp_success_response(
+        "Stale documents found",
+        Some(json!({
+            "collectionId": collection_id,
+            "notUpdatedSince": cutoff,
+            "checked": total,
+            "truncated": truncated,
+            "total": total,
+            "data": report
+        })),
+    ))
+}
+
+async fn find_orphan_documents(args: Value, client: &OutlineClient) -> Result<Value> {
+    let collection_id = get_string_arg(&args, "collection_id")?;
+
+    debug!("Finding orphan documents in collection: {}", collection_id);
+
+    let tree = client
+        .post("collections.documents", json!({ "id": collection_id }))
+        .await?;
+    let roots = tree["data"].as_array().cloned().unwrap_or_default();
+
+    let mut orphans = Vec::new();
+    for node in roots.iter().take(MAX_REPORT_CANDIDATES) {
+        let id = node["id"].as_str().unwrap_or_default();
+        let backlinks = client
+            .post(
+                "documents.list",
+                json!({ "backlinkDocumentId": id, "limit": 1 }),
+            )
+            .await?;
+
+        if backlinks["data"].as_array().map_or(true, Vec::is_empty) {
+            orphans.push(json!({
+                "id": id,
+                "title": node["title"],
+                "url": node["url"],
+                "children": node["children"].as_array().map_or(0, Vec::len)
+            }));
+        }
+    }
+
+    Ok(create_mcp_success_response(
+        "Orphan documents found",
+        Some(json!({
+            "collectionId": collection_id,
+            "checked": roots.len().min(MAX_REPORT_CANDIDATES),
+            "total": orphans.len(),
+            "data": orphans
+        })),
+    ))
+}
+
+/// Total views, distinct viewers and latest view time from `views.list` entries
+fn summarize_views(views: &[Value]) -> Value {
+    let total: u64 = views.iter().filter_map(|v| v["count"].as_u64()).sum();
+    let last_viewed = views.iter().filter_map(|v| v["updatedAt"].as_str()).max();
+
+    json!({
+        "views": total,
+        "viewers": views.len(),
+        "lastViewedAt": last_viewed
+    })
+}
+
+/// Sort by views (descending), least recently updated first on ties
+fn rank_by_views(report: &mut [Value]) {
+    report.sort_by(|a, b| {
+        b["views"]
+            .as_u64()
+            .cmp(&a["views"].as_u64())
+            .then_with(|| a["updatedAt"].as_str().cmp(&b["updatedAt"].as_str()))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::stand_in;
+
+    #[tokio::test]
+    async fn test_stale_days_saturate() {
+        let client = stand_in::serve(vec![(
+            "documents.list",
+            stand_in::fixed(json!({ "data": [] })),
+        )])
+        .await;
+        let args = json!({ "collection_id": "col-1", "days": i64::MAX });
+
+        let response = find_stale_documents(args, &client).await.unwrap();
+        let content = &response["structuredContent"];
+        assert!(content["notUpdatedSince"]
+            .as_str()
+            .unwrap()
+            .starts_with("1970-01-01"));
+        assert_eq!(content["truncated"], false);
+    }
+
+    #[tokio::test]
+    async fn test_stale_report_flags_candidate_cap() {
+        let client = stand_in::serve(vec![
+            (
+                "documents.list",
+                stand_in::fixed(json!({
+                    "data": vec![json!({ "id": "d", "updatedAt": "2000-01-01T00:00:00.000Z" }); DOCUMENTS_PAGE_SIZE]
+                })),
+            ),
+            ("views.list", stand_in::fixed(json!({ "data": [] }))),
+        ])
+        .await;
+
+        let response = find_stale_documents(json!({ "collection_id": "col-1" }), &client)
+            .await
+            .unwrap();
+        let content = &response["structuredContent"];
+        assert_eq!(content["truncated"], true);
+        assert_eq!(content["checked"], MAX_REPORT_CANDIDATES);
+    }
+
+    #[test]
+    fn test_summarize_views() {
+        let views = vec![
+            json!({ "count": 3, "updatedAt": "2024-01-02T00:00:00.000Z" }),
+            json!({ "count": 2, "updatedAt": "2024-03-01T00:00:00.000Z" }),
+        ];
+        let summary = summarize_views(&views);
+        assert_eq!(summary["views"], 5);
+        assert_eq!(summary["viewers"], 2);
+        assert_eq!(summary["lastViewedAt"], "2024-03-01T00:00:00.000Z");
+
+        let empty = summarize_views(&[]);
+        assert_eq!(empty["views"], 0);
+        assert!(empty["lastViewedAt"].is_null());
+    }
+
+    #[test]
+    fn test_rank_by_views() {
+        let mut report = vec![
+            json!({ "id": "a", "views": 1, "updatedAt": "2024-01-01" }),
+            json!({ "id": "b", "views": 9, "updatedAt": "2024-01-05" }),
+            json!({ "id": "c", "views": 9, "updatedAt": "2023-12-01" }),
+        ];
+        rank_by_views(&mut report);
+        let ids: Vec<_> = report.iter().map(|r| r["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["c", "b", "a"]);
+    }
+}
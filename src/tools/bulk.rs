@@ -0,0 +1,421 @@
+//! Bulk document operations
+//!
+//! Runs single-document operations for many IDs with bounded concurrency and
+//! reports the outcome for every ID.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use futures_util::stream::{self, StreamExt};
+use serde_json::{json, Value};
+use tracing::debug;
+
+use super::common::{
+    create_mcp_success_response, get_optional_bool_arg, get_optional_number_arg,
+    get_optional_string_arg, get_optional_string_array_arg, tool_definition, with_optional_params,
+};
+use crate::error::{Error, Result};
+use crate::outline::{update_document_request, Client as OutlineClient};
+
+/// Default number of requests in flight
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Upper bound for the `concurrency` argument
+const MAX_CONCURRENCY: usize = 10;
+
+/// Shared options of all bulk tools
+#[derive(Debug, Clone, Copy)]
+struct BulkOptions {
+    concurrency: usize,
+    stop_on_error: bool,
+    dry_run: bool,
+}
+
+impl BulkOptions {
+    fn from_args(args: &Value) -> Self {
+        let concurrency = get_optional_number_arg(args, "concurrency")
+            .and_then(|c| usize::try_from(c).ok())
+            .unwrap_or(DEFAULT_CONCURRENCY)
+            .clamp(1, MAX_CONCURRENCY);
+
+        Self {
+            concurrency,
+            stop_on_error: get_optional_bool_arg(args, "stop_on_error").unwrap_or(false),
+            dry_run: get_optional_bool_arg(args, "dry_run").unwrap_or(false),
+        }
+    }
+}
+
+/// Parameters shared by all bulk tools
+const COMMON_PARAMS: [(&str, &str, &str); 3] = [
+    (
+        "concurrency",
+        "number",
+        "Requests in flight, 1-10, default 4 (optional)",
+    ),
+    (
+        "stop_on_error",
+        "boolean",
+        "Stop starting new operations after the first failure (optional)",
+    ),
+    (
+        "dry_run",
+        "boolean",
+        "Only resolve documents and report planned changes (optional)",
+    ),
+];
+
+/// Get all bulk tool definitions
+pub fn get_bulk_tools() -> Vec<Value> {
+    let bulk_tool = |name: &str,
+                     description: &str,
+                     required: &[(&str, &str, &str)],
+                     optional: &[(&str, &str, &str)]| {
+        let tool = with_optional_params(tool_definition(name, description, required), optional);
+        with_optional_params(tool, &COMMON_PARAMS)
+    };
+
+    let mut update = bulk_tool(
+        "bulk_update_documents",
+        "Update title and/or content of many documents",
+        &[(
+            "updates",
+            "array",
+            "Updates as objects with 'id' and optional 'title' and 'text'",
+        )],
+        &[],
+    );
+    update["inputSchema"]["properties"]["updates"]["items"] = json!({
+        "type": "object",
+        "properties": {
+            "id": { "type": "string" },
+            "title": { "type": "string" },
+            "text": { "type": "string" }
+        },
+        "required": ["id"]
+    });
+
+    vec![
+        bulk_tool(
+            "bulk_move_documents",
+            "Move many documents to a collection or parent document",
+            &[("ids", "array", "Document IDs")],
+            &[
+                ("collection_id", "string", "Target collection ID (optional)"),
+                (
+                    "parent_document_id",
+                    "string",
+                    "New parent document ID (optional)",
+                ),
+            ],
+        ),
+        bulk_tool(
+            "bulk_archive_documents",
+            "Archive many documents",
+            &[("ids", "array", "Document IDs")],
+            &[],
+        ),
+        bulk_tool(
+            "bulk_delete_documents",
+            "Delete many documents",
+            &[("ids", "array", "Document IDs")],
+            &[],
+        ),
+        update,
+    ]
+}
+
+/// Call bulk tool
+pub async fn call_bulk_tool(name: &str, arguments: Value, client: &OutlineClient) -> Result<Value> {
+    match name {
+        "bulk_move_documents" => bulk_move_documents(arguments, client).await,
+        "bulk_archive_documents" => bulk_simple(arguments, client, name, "documents.archive").await,
+        "bulk_delete_documents" => bulk_simple(arguments, client, name, "documents.delete").await,
+        "bulk_update_documents" => bulk_update_documents(arguments, client).await,
+        _ => unreachable!("Unknown bulk tool: {}", name),
+    }
+}
+
+async fn bulk_move_documents(args: Value, client: &OutlineClient) -> Result<Value> {
+    let ids = get_ids(&args, "bulk_move_documents")?;
+    let collection_id = get_optional_string_arg(&args, "collection_id");
+    let parent_document_id = get_optional_string_arg(&args, "parent_document_id");
+    let options = BulkOptions::from_args(&args);
+
+    if collection_id.is_none() && parent_document_id.is_none() {
+        return Err(bulk_error(
+            "bulk_move_documents",
+            "Either 'collection_id' or 'parent_document_id' is required",
+        ));
+    }
+
+    debug!("Bulk moving {} documents", ids.len());
+
+    let mut template = json!({});
+    if let Some(cid) = collection_id {
+        template["collectionId"] = json!(cid);
+    }
+    if let Some(pid) = parent_document_id {
+        template["parentDocumentId"] = json!(pid);
+    }
+
+    let report = run_bulk(client, ids, options, |id| {
+        let mut body = template.clone();
+        body["id"] = json!(id);
+        async move { client.post("documents.move", body).await }
+    })
+    .await;
+
+    Ok(create_mcp_success_response(
+        "Bulk move finished",
+        Some(report),
+    ))
+}
+
+/// Bulk operation that only needs the document ID
+async fn bulk_simple(
+    args: Value,
+    client: &OutlineClient,
+    tool_name: &str,
+    endpoint: &str,
+) -> Result<Value> {
+    let ids = get_ids(&args, tool_name)?;
+    let options = BulkOptions::from_args(&args);
+
+    debug!("{} via {} for {} documents", tool_name, endpoint, ids.len());
+
+    let report = run_bulk(client, ids, options, |id| async move {
+        client.post(endpoint, json!({ "id": id })).await
+    })
+    .await;
+
+    Ok(create_mcp_success_response(
+        &format!("{tool_name} finished"),
+        Some(report),
+    ))
+}
+
+async fn bulk_update_documents(args: Value, client: &OutlineClient) -> Result<Value> {
+    let updates = args
+        .get("updates")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let options = BulkOptions::from_args(&args);
+
+    let mut ids = Vec::with_capacity(updates.len());
+    for update in &updates {
+        let id = update["id"].as_str().ok_or_else(|| {
+            bulk_error(
+                "bulk_update_documents",
+                "Every item in 'updates' requires an 'id'",
+            )
+        })?;
+        if ids.iter().any(|seen| seen == id) {
+            return Err(bulk_error(
+                "bulk_update_documents",
+                &format!("Document {id} appears more than once in 'updates'"),
+            ));
+        }
+        ids.push(id.to_string());
+    }
+    if ids.is_empty() {
+        return Err(bulk_error(
+            "bulk_update_documents",
+            "Missing required parameter 'updates'",
+        ));
+    }
+
+    debug!("Bulk updating {} documents", ids.len());
+
+    let report = run_bulk(client, ids, options, |id| {
+        let update = updates
+            .iter()
+            .find(|u| u["id"] == id.as_str())
+            .cloned()
+            .unwrap_or_default();
+        let body = update_document_request(&id, update["title"].as_str(), update["text"].as_str());
+        async move { client.post("documents.update", body).await }
+    })
+    .await;
+
+    Ok(create_mcp_success_response(
+        "Bulk update finished",
+        Some(report),
+    ))
+}
+
+/// Run `operation` for every ID and build the per-ID report
+///
+/// In dry-run mode each document is only looked up with `documents.info`.
+/// With `stop_on_error`, operations already in flight finish but no new ones
+/// are started after the first failure; the remaining IDs are reported as skipped.
+async fn run_bulk<F, Fut>(
+    client: &OutlineClient,
+    ids: Vec<String>,
+    options: BulkOptions,
+    operation: F,
+) -> Value
+where
+    F: Fn(String) -> Fut + Sync,
+    Fut: Future<Output = Result<Value>> + Send,
+{
+    let stopped = AtomicBool::new(false);
+    let total = ids.len();
+
+    let mut results: Vec<Value> = stream::iter(ids.iter().cloned())
+        .take_while(|_| std::future::ready(!stopped.load(Ordering::Relaxed)))
+        .map(|id| {
+            let future = if options.dry_run {
+                None
+            } else {
+                Some(operation(id.clone()))
+            };
+            async move {
+                let result = match future {
+                    Some(future) => future.await,
+                    None => client.post("documents.info", json!({ "id": id })).await,
+                };
+                (id, result)
+            }
+        })
+        .buffered(options.concurrency)
+        .map(|(id, result)| match result {
+            Ok(response) => json!({
+                "id": id,
+                "status": if options.dry_run { "planned" } else { "ok" },
+                "title": response["data"]["title"]
+            }),
+            Err(e) => {
+                if options.stop_on_error {
+                    stopped.store(true, Ordering::Relaxed);
+                }
+                json!({
+                    "id": id,
+                    "status": "error",
+                    "error": e.to_string()
+                })
+            }
+        })
+        .collect()
+        .await;
+
+    for id in ids.iter().skip(results.len()) {
+        results.push(json!({ "id": id, "status": "skipped" }));
+    }
+
+    let count = |status: &str| results.iter().filter(|r| r["status"] == status).count();
+
+    json!({
+        "total": total,
+        "succeeded": count("ok") + count("planned"),
+        "failed": count("error"),
+        "skipped": count("skipped"),
+        "dryRun": options.dry_run,
+        "data": results
+    })
+}
+
+fn get_ids(args: &Value, tool_name: &str) -> Result<Vec<String>> {
+    get_optional_string_array_arg(args, "ids")
+        .filter(|ids| !ids.is_empty())
+        .ok_or_else(|| bulk_error(tool_name, "Missing required parameter 'ids'"))
+}
+
+fn bulk_error(tool_name: &str, message: &str) -> Error {
+    Error::Tool {
+        tool_name: tool_name.to_string(),
+        message: message.to_string(),
+        source: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::stand_in;
+
+    #[test]
+    fn test_bulk_tools_require_only_targets() {
+        let required: Vec<Value> = get_bulk_tools()
+            .iter()
+            .map(|tool| tool["inputSchema"]["required"].clone())
+            .collect();
+        assert_eq!(
+            required,
+            [
+                json!(["ids"]),
+                json!(["ids"]),
+                json!(["ids"]),
+                json!(["updates"])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bulk_update_items_are_objects() {
+        let tools = get_bulk_tools();
+        let updates = &tools[3]["inputSchema"]["properties"]["updates"];
+        assert_eq!(updates["items"]["type"], "object");
+        assert_eq!(updates["items"]["required"], json!(["id"]));
+        assert_eq!(
+            tools[0]["inputSchema"]["properties"]["ids"]["items"]["type"],
+            "string"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bulk_simple_errors_name_the_tool() {
+        let client = stand_in::serve(Vec::new()).await;
+        let err = call_bulk_tool("bulk_archive_documents", json!({}), &client)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("bulk_archive_documents"));
+    }
+
+    #[tokio::test]
+    async fn test_bulk_update_rejects_duplicate_ids() {
+        let client = stand_in::serve(Vec::new()).await;
+        let args = json!({
+            "updates": [
+                { "id": "doc-1", "title": "First" },
+                { "id": "doc-1", "title": "Second" }
+            ]
+        });
+
+        let err = bulk_update_documents(args, &client).await.unwrap_err();
+        assert!(err.to_string().contains("appears more than once"));
+    }
+
+    #[test]
+    fn test_bulk_options_from_args() {
+        let options = BulkOptions::from_args(&json!({}));
+        assert_eq!(options.concurrency, DEFAULT_CONCURRENCY);
+        assert!(!options.stop_on_error);
+        assert!(!options.dry_run);
+
+        let options = BulkOptions::from_args(&json!({
+            "concurrency": 50,
+            "stop_on_error": true,
+            "dry_run": true
+        }));
+        assert_eq!(options.concurrency, MAX_CONCURRENCY);
+        assert!(options.stop_on_error);
+        assert!(options.dry_run);
+
+        assert_eq!(
+            BulkOptions::from_args(&json!({ "concurrency": 0 })).concurrency,
+            1
+        );
+    }
+
+    #[test]
+    fn test_get_ids() {
+        assert_eq!(
+            get_ids(&json!({ "ids": ["a", "b"] }), "t").unwrap(),
+            vec!["a", "b"]
+        );
+        assert!(get_ids(&json!({ "ids": [] }), "t").is_err());
+        assert!(get_ids(&json!({}), "t").is_err());
+    }
+}
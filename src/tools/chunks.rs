@@ -0,0 +1,147 @@
+//! Document chunking helpers
+//!
+//! Splits long markdown into numbered byte ranges, either by size alone or
+//! at top-level headings, for reading documents piece by piece.
+
+use std::ops::Range;
+
+/// A numbered piece of a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// Byte range within the document text
+    pub range: Range<usize>,
+    /// Heading of the section the chunk belongs to
+    pub heading: Option<String>,
+}
+
+/// Split `text` into ranges of at most `size` bytes
+///
+/// Cuts fall on line breaks when one lies in the second half of the window,
+/// and always on character boundaries.
+pub fn split_by_bytes(text: &str, size: usize) -> Vec<Range<usize>> {
+    split_range(text, 0..text.len(), size.max(1))
+}
+
+/// Split `text` into sections starting at `#` and `##` headings
+///
+/// Sections longer than `size` bytes are split further by size.
+pub fn split_by_headings(text: &str, size: usize) -> Vec<Chunk> {
+    let mut starts: Vec<(usize, Option<String>)> = vec![(0, None)];
+    let mut offset = 0;
+    let mut in_fence = false;
+
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            if let Some(heading) = section_heading(line) {
+                starts.push((offset, Some(heading)));
+            }
+        }
+        offset += line.len();
+    }
+
+    let mut chunks = Vec::new();
+    for (index, (start, heading)) in starts.iter().enumerate() {
+        let end = starts.get(index + 1).map_or(text.len(), |(next, _)| *next);
+        // Text before the first heading is often empty
+        if text[*start..end].trim().is_empty() {
+            continue;
+        }
+        for range in split_range(text, *start..end, size.max(1)) {
+            chunks.push(Chunk {
+                range,
+                heading: heading.clone(),
+            });
+        }
+    }
+    if chunks.is_empty() {
+        chunks.push(Chunk {
+            range: 0..text.len(),
+            heading: None,
+        });
+    }
+    chunks
+}
+
+/// Title of a level 1 or 2 markdown heading
+fn section_heading(line: &str) -> Option<String> {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    let title = line[hashes..].strip_prefix(' ')?.trim();
+    (matches!(hashes, 1 | 2) && !title.is_empty()).then(|| title.to_string())
+}
+
+fn split_range(text: &str, range: Range<usize>, size: usize) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = range.start;
+
+    while range.end - start > size {
+        let mut end = start + size;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        if let Some(newline) = text[start..end].rfind('\n') {
+            if newline + 1 > size / 2 {
+                end = start + newline + 1;
+            }
+        }
+        // A single character wider than `size` still has to move forward
+        if end == start {
+            end = start + text[start..].chars().next().map_or(1, char::len_utf8);
+        }
+        ranges.push(start..end);
+        start = end;
+    }
+    if start < range.end || ranges.is_empty() {
+        ranges.push(start..range.end);
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_by_bytes() {
+        let text = "line one\nline two\nline three";
+        let ranges = split_by_bytes(text, 12);
+        let parts: Vec<&str> = ranges.iter().map(|r| &text[r.clone()]).collect();
+        assert_eq!(parts, vec!["line one\n", "line two\n", "line three"]);
+
+        // Multi-byte characters are never cut
+        let text = "ññññ";
+        let parts: Vec<&str> = split_by_bytes(text, 3)
+            .into_iter()
+            .map(|r| &text[r])
+            .collect();
+        assert_eq!(parts, vec!["ñ", "ñ", "ñ", "ñ"]);
+
+        assert_eq!(split_by_bytes("", 10), vec![0..0]);
+    }
+
+    #[test]
+    fn test_split_by_headings() {
+        let text = "# Spec\nIntro\n## API\n```\n# not a heading\n```\n### Detail\n## Notes\nEnd";
+        let chunks = split_by_headings(text, 1000);
+        let headings: Vec<_> = chunks.iter().map(|c| c.heading.as_deref()).collect();
+        assert_eq!(headings, vec![Some("Spec"), Some("API"), Some("Notes")]);
+        assert!(text[chunks[1].range.clone()].contains("### Detail"));
+        assert_eq!(&text[chunks[2].range.clone()], "## Notes\nEnd");
+
+        assert_eq!(split_by_headings("", 1000).len(), 1);
+    }
+
+    #[test]
+    fn test_split_by_headings_splits_long_sections() {
+        let text = format!("Preface\n# Long\n{}", "word\n".repeat(10));
+        let chunks = split_by_headings(&text, 20);
+        assert_eq!(chunks[0].heading, None);
+        assert!(chunks.len() > 3);
+        assert!(chunks[1..]
+            .iter()
+            .all(|c| c.heading.as_deref() == Some("Long")));
+        assert_eq!(chunks.last().unwrap().range.end, text.len());
+    }
+}
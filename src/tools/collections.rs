@@ -1,15 +1,21 @@
 //! Collection management tools
 
+use std::fmt::Write;
+
 use serde_json::{json, Value};
 use tracing::debug;
 
 use super::common::{
-    create_mcp_success_response, get_optional_number_arg, get_optional_string_arg, get_string_arg,
-    tool_definition,
+    create_mcp_success_response, get_optional_bool_arg, get_optional_number_arg,
+    get_optional_string_arg, get_string_arg, tool_definition, with_optional_params,
 };
-use crate::error::Result;
+use super::navigation::{find_navigation_node, render_outline};
+use crate::error::{Error, Result};
 use crate::outline::{create_collection_request, Client as OutlineClient};
 
+/// Collections fetched per `collections.list` page
+const COLLECTIONS_PAGE_SIZE: usize = 100;
+
 /// Get all collection tool definitions
 pub fn get_collection_tools() -> Vec<Value> {
     vec![
@@ -26,13 +32,41 @@ pub fn get_collection_tools() -> Vec<Value> {
             "Get collection",
             &[("id", "string", "Collection ID")],
         ),
-        tool_definition(
-            "update_collection",
-            "Update collection",
+        with_optional_params(
+            tool_definition(
+                "update_collection",
+                "Update collection",
+                &[("id", "string", "Collection ID")],
+            ),
             &[
-                ("id", "string", "Collection ID"),
                 ("name", "string", "New name (optional)"),
                 ("description", "string", "New description (optional)"),
+                ("icon", "string", "Icon name or emoji (optional)"),
+                (
+                    "color",
+                    "string",
+                    "Icon color as hex, e.g. #FF5C80 (optional)",
+                ),
+                (
+                    "permission",
+                    "string",
+                    "Default workspace access: read, read_write or none (optional)",
+                ),
+                (
+                    "sharing",
+                    "boolean",
+                    "Allow public sharing of documents (optional)",
+                ),
+                (
+                    "sort",
+                    "string",
+                    "Document order: index (manual) or title (optional)",
+                ),
+                (
+                    "sort_direction",
+                    "string",
+                    "Sort direction: asc (default) or desc (optional)",
+                ),
             ],
         ),
         tool_definition(
@@ -50,6 +84,32 @@ pub fn get_collection_tools() -> Vec<Value> {
             "Get document structure of a collection",
             &[("id", "string", "Collection ID")],
         ),
+        tool_definition(
+            "get_collection_tree",
+            "Render document hierarchy as a markdown outline with titles, IDs and URLs",
+            &[
+                (
+                    "collection_id",
+                    "string",
+                    "Collection ID (optional, omit to render the whole workspace)",
+                ),
+                (
+                    "root_document_id",
+                    "string",
+                    "Render only the subtree under this document (optional)",
+                ),
+                (
+                    "max_depth",
+                    "number",
+                    "Maximum levels to render, 1 = top level only (optional)",
+                ),
+                (
+                    "filter",
+                    "string",
+                    "Case-insensitive title filter, ancestors of matches are kept (optional)",
+                ),
+            ],
+        ),
     ]
 }
 
@@ -66,6 +126,7 @@ pub async fn call_collection_tool(
         "list_collections" => list_collections(arguments, client).await,
         "delete_collection" => delete_collection(arguments, client).await,
         "get_collection_documents" => get_collection_documents(arguments, client).await,
+        "get_collection_tree" => get_collection_tree(arguments, client).await,
         _ => unreachable!("Unknown collection tool: {}", name),
     }
 }
@@ -113,6 +174,40 @@ async fn update_collection(args: Value, client: &OutlineClient) -> Result<Value>
     if let Some(d) = description {
         request_body["description"] = json!(d);
     }
+    for field in ["icon", "color"] {
+        if let Some(value) = get_optional_string_arg(&args, field) {
+            request_body[field] = json!(value);
+        }
+    }
+    if let Some(permission) = get_optional_string_arg(&args, "permission") {
+        request_body["permission"] = match permission.as_str() {
+            "read" | "read_write" => json!(permission),
+            "none" => Value::Null,
+            other => {
+                return Err(collection_update_error(format!(
+                    "Invalid permission '{other}', expected read, read_write or none"
+                )))
+            }
+        };
+    }
+    if let Some(sharing) = get_optional_bool_arg(&args, "sharing") {
+        request_body["sharing"] = json!(sharing);
+    }
+    if let Some(field) = get_optional_string_arg(&args, "sort") {
+        let direction =
+            get_optional_string_arg(&args, "sort_direction").unwrap_or_else(|| "asc".into());
+        if !matches!(field.as_str(), "index" | "title") {
+            return Err(collection_update_error(format!(
+                "Invalid sort '{field}', expected index or title"
+            )));
+        }
+        if !matches!(direction.as_str(), "asc" | "desc") {
+            return Err(collection_update_error(format!(
+                "Invalid sort_direction '{direction}', expected asc or desc"
+            )));
+        }
+        request_body["sort"] = json!({ "field": field, "direction": direction });
+    }
 
     let response = client.post("collections.update", request_body).await?;
 
@@ -166,3 +261,164 @@ async fn get_collection_documents(args: Value, client: &OutlineClient) -> Result
         Some(response),
     ))
 }
+
+async fn get_collection_tree(args: Value, client: &OutlineClient) -> Result<Value> {
+    let collection_id = get_optional_string_arg(&args, "collection_id");
+    let root_document_id = get_optional_string_arg(&args, "root_document_id");
+    let max_depth =
+        get_optional_number_arg(&args, "max_depth").and_then(|d| usize::try_from(d).ok());
+    let filter = get_optional_string_arg(&args, "filter");
+
+    debug!("Rendering collection tree: {:?}", collection_id);
+
+    // Resolve the collection from the root document when only the latter is given
+    let collection_id = match (collection_id, &root_document_id) {
+        (Some(cid), _) => Some(cid),
+        (None, Some(doc_id)) => {
+            let document = client
+                .post("documents.info", json!({ "id": doc_id }))
+                .await?;
+            let cid = document["data"]["collectionId"]
+                .as_str()
+                .ok_or_else(|| Error::Tool {
+                    tool_name: "get_collection_tree".to_string(),
+                    message: format!(
+                        "Document {doc_id} is not in a collection (drafts have no tree); pass collection_id to use another collection"
+                    ),
+                    source: None,
+                })?;
+            Some(cid.to_string())
+        }
+        (None, None) => None,
+    };
+
+    let collections = if let Some(cid) = collection_id {
+        let response = client
+            .post("collections.info", json!({ "id": cid }))
+            .await?;
+        vec![response["data"].clone()]
+    } else {
+        let mut collections = Vec::new();
+        loop {
+            let response = client
+                .post(
+                    "collections.list",
+                    json!({ "limit": COLLECTIONS_PAGE_SIZE, "offset": collections.len() }),
+                )
+                .await?;
+            let page = response["data"].as_array().cloned().unwrap_or_default();
+            let page_len = page.len();
+            collections.extend(page);
+            if page_len < COLLECTIONS_PAGE_SIZE {
+                break;
+            }
+        }
+        collections
+    };
+
+    let mut outline = String::new();
+
+    for collection in &collections {
+        let Some(cid) = collection["id"].as_str() else {
+            continue;
+        };
+        let name = collection["name"].as_str().unwrap_or("Untitled collection");
+
+        let navigation = client
+            .post("collections.documents", json!({ "id": cid }))
+            .await?;
+        let nodes = navigation["data"].as_array().map_or(&[][..], Vec::as_slice);
+
+        let nodes = match &root_document_id {
+            Some(root_id) => match find_navigation_node(nodes, root_id) {
+                Some(root) => std::slice::from_ref(root),
+                None => {
+                    return Err(Error::Tool {
+                        tool_name: "get_collection_tree".to_string(),
+                        message: format!("Document {root_id} not found in collection {cid}"),
+                        source: None,
+                    })
+                }
+            },
+            None => nodes,
+        };
+
+        let rendered = render_outline(nodes, max_depth, filter.as_deref());
+        if filter.is_some() && rendered.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(outline, "## {name} (`{cid}`)\n");
+        if rendered.is_empty() {
+            outline.push_str("_No documents_\n");
+        } else {
+            outline.push_str(&rendered);
+        }
+        outline.push('\n');
+    }
+
+    if outline.is_empty() {
+        outline.push_str("No matching documents found\n");
+    }
+
+    Ok(create_mcp_success_response(outline.trim_end(), None))
+}
+
+fn collection_update_error(message: String) -> Error {
+    Error::Tool {
+        tool_name: "update_collection".to_string(),
+        message,
+        source: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::stand_in;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_tree_of_draft_root_document() {
+        let client = stand_in::serve(vec![(
+            "documents.info",
+            stand_in::fixed(json!({ "data": { "id": "doc-1", "collectionId": null } })),
+        )])
+        .await;
+
+        let err = get_collection_tree(json!({ "root_document_id": "doc-1" }), &client)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not in a collection"));
+    }
+
+    #[tokio::test]
+    async fn test_tree_covers_every_collection_page() {
+        let client = stand_in::serve(vec![
+            (
+                "collections.list",
+                Arc::new(|body: &Value| {
+                    let offset = body["offset"].as_u64().unwrap_or(0);
+                    let count = if offset == 0 {
+                        COLLECTIONS_PAGE_SIZE as u64
+                    } else {
+                        1
+                    };
+                    let page: Vec<Value> = (offset..offset + count)
+                        .map(|i| json!({ "id": format!("col-{i}"), "name": format!("C{i}") }))
+                        .collect();
+                    stand_in::ok(&json!({ "data": page }))
+                }),
+            ),
+            (
+                "collections.documents",
+                stand_in::fixed(json!({ "data": [] })),
+            ),
+        ])
+        .await;
+
+        let response = get_collection_tree(json!({}), &client).await.unwrap();
+        let text = response["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains(&format!("`col-{COLLECTIONS_PAGE_SIZE}`")));
+    }
+}
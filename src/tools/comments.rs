@@ -1,31 +1,66 @@
 //! Comment management tools
+//!
+//! Comments are created from markdown and can be replies to another comment.
+//! Outline returns comment bodies as `ProseMirror` JSON, which is flattened to
+//! text when a discussion is rendered as markdown. `@mentions` in markdown are
+//! resolved to users so they get notified.
+
+use std::collections::HashMap;
+use std::fmt::Write;
 
 use serde_json::{json, Value};
 use tracing::debug;
 
 use super::common::{
-    create_mcp_success_response, get_optional_number_arg, get_string_arg, tool_definition,
+    create_mcp_success_response, get_optional_bool_arg, get_optional_number_arg,
+    get_optional_string_arg, get_string_arg, tool_definition, with_optional_params,
 };
-use crate::error::Result;
+use super::mentions::{resolve_mentions_in_args, MENTIONS_PARAM};
+use crate::error::{Error, Result};
 use crate::outline::{create_comment_request, Client as OutlineClient};
 
+/// Page size used when fetching a whole discussion
+const COMMENTS_PAGE_SIZE: usize = 100;
+
 /// Get all comment tool definitions
+#[allow(clippy::too_many_lines)]
 pub fn get_comment_tools() -> Vec<Value> {
     vec![
-        tool_definition(
-            "create_comment",
-            "Create comment",
+        with_optional_params(
+            tool_definition(
+                "create_comment",
+                "Create comment or reply",
+                &[("document_id", "string", "Document ID")],
+            ),
             &[
-                ("document_id", "string", "Document ID"),
-                ("data", "string", "Comment content"),
+                ("text", "string", "Comment content as markdown (optional if data is set)"),
+                (
+                    "parent_comment_id",
+                    "string",
+                    "Comment ID to reply to (optional)",
+                ),
+                (
+                    "data",
+                    "object",
+                    "ProseMirror JSON content instead of text (optional)",
+                ),
+                MENTIONS_PARAM,
             ],
         ),
-        tool_definition(
-            "update_comment",
-            "Update comment",
+        with_optional_params(
+            tool_definition(
+                "update_comment",
+                "Update comment",
+                &[("id", "string", "Comment ID")],
+            ),
             &[
-                ("id", "string", "Comment ID"),
-                ("data", "string", "New content"),
+                ("text", "string", "New content as markdown (optional if data is set)"),
+                (
+                    "data",
+                    "object",
+                    "ProseMirror JSON content instead of text (optional)",
+                ),
+                MENTIONS_PARAM,
             ],
         ),
         tool_definition(
@@ -34,13 +69,67 @@ pub fn get_comment_tools() -> Vec<Value> {
             &[("id", "string", "Comment ID")],
         ),
         tool_definition(
-            "list_document_comments",
-            "List comments for a document",
+            "add_comment_reaction",
+            "Add emoji reaction to comment",
+            &[
+                ("id", "string", "Comment ID"),
+                ("emoji", "string", "Emoji, e.g. 👍"),
+            ],
+        ),
+        tool_definition(
+            "remove_comment_reaction",
+            "Remove own emoji reaction from comment",
+            &[
+                ("id", "string", "Comment ID"),
+                ("emoji", "string", "Emoji to remove"),
+            ],
+        ),
+        tool_definition(
+            "resolve_comment",
+            "Mark comment thread as resolved",
+            &[("id", "string", "Comment ID of the thread")],
+        ),
+        tool_definition(
+            "unresolve_comment",
+            "Reopen resolved comment thread",
+            &[("id", "string", "Comment ID of the thread")],
+        ),
+        with_optional_params(
+            tool_definition(
+                "list_document_comments",
+                "List comments for a document",
+                &[("document_id", "string", "Document ID")],
+            ),
             &[
-                ("document_id", "string", "Document ID"),
-                ("limit", "number", "Number of comments (optional)"),
+                (
+                    "limit",
+                    "number",
+                    "Number of comments, counted after the status filter (optional)",
+                ),
+                (
+                    "status",
+                    "string",
+                    "Thread status: resolved, unresolved or all (optional, default all)",
+                ),
+                (
+                    "threaded",
+                    "boolean",
+                    "Group replies under their thread as 'replies', limit then counts threads (optional)",
+                ),
             ],
         ),
+        with_optional_params(
+            tool_definition(
+                "get_document_discussion",
+                "Render all comment threads of a document as markdown with authors",
+                &[("document_id", "string", "Document ID")],
+            ),
+            &[(
+                "status",
+                "string",
+                "Thread status: resolved, unresolved or all (optional, default all)",
+            )],
+        ),
         tool_definition(
             "get_comment",
             "Get comment by ID",
@@ -59,7 +148,12 @@ pub async fn call_comment_tool(
         "create_comment" => create_comment(arguments, client).await,
         "update_comment" => update_comment(arguments, client).await,
         "delete_comment" => delete_comment(arguments, client).await,
+        "add_comment_reaction" => comment_reaction(arguments, client, true).await,
+        "remove_comment_reaction" => comment_reaction(arguments, client, false).await,
+        "resolve_comment" => set_comment_resolved(arguments, client, true).await,
+        "unresolve_comment" => set_comment_resolved(arguments, client, false).await,
         "list_document_comments" => list_document_comments(arguments, client).await,
+        "get_document_discussion" => get_document_discussion(arguments, client).await,
         "get_comment" => get_comment(arguments, client).await,
         _ => unreachable!("Unknown comment tool: {}", name),
     }
@@ -67,12 +161,32 @@ pub async fn call_comment_tool(
 
 async fn create_comment(args: Value, client: &OutlineClient) -> Result<Value> {
     let document_id = get_string_arg(&args, "document_id")?;
-    let data = get_string_arg(&args, "data")?;
+    let parent_comment_id = get_optional_string_arg(&args, "parent_comment_id");
 
     debug!("Creating comment for document: {}", document_id);
 
-    let request_body = create_comment_request(&document_id, &data);
-    let response = client.post("comments.create", request_body).await?;
+    let mut mentions = None;
+    let request_body = match comment_content(&args, "create_comment")? {
+        CommentContent::Markdown(text) => {
+            let (text, resolved) = resolve_mentions_in_args(&args, client, text).await;
+            mentions = resolved;
+            create_comment_request(&document_id, &text, parent_comment_id.as_deref())
+        }
+        CommentContent::ProseMirror(data) => {
+            let mut body = json!({
+                "documentId": document_id,
+                "data": data
+            });
+            if let Some(parent) = parent_comment_id {
+                body["parentCommentId"] = json!(parent);
+            }
+            body
+        }
+    };
+    let mut response = client.post("comments.create", request_body).await?;
+    if let Some(mentions) = mentions {
+        mentions.annotate(&mut response);
+    }
 
     Ok(create_mcp_success_response(
         "Comment created successfully",
@@ -82,15 +196,28 @@ async fn create_comment(args: Value, client: &OutlineClient) -> Result<Value> {
 
 async fn update_comment(args: Value, client: &OutlineClient) -> Result<Value> {
     let id = get_string_arg(&args, "id")?;
-    let data = get_string_arg(&args, "data")?;
 
     debug!("Updating comment: {}", id);
 
-    let request_body = json!({
-        "id": id,
-        "data": data
-    });
-    let response = client.post("comments.update", request_body).await?;
+    let mut mentions = None;
+    let request_body = match comment_content(&args, "update_comment")? {
+        CommentContent::Markdown(text) => {
+            let (text, resolved) = resolve_mentions_in_args(&args, client, text).await;
+            mentions = resolved;
+            json!({
+                "id": id,
+                "text": text
+            })
+        }
+        CommentContent::ProseMirror(data) => json!({
+            "id": id,
+            "data": data
+        }),
+    };
+    let mut response = client.post("comments.update", request_body).await?;
+    if let Some(mentions) = mentions {
+        mentions.annotate(&mut response);
+    }
 
     Ok(create_mcp_success_response(
         "Comment updated successfully",
@@ -112,18 +239,93 @@ async fn delete_comment(args: Value, client: &OutlineClient) -> Result<Value> {
     ))
 }
 
+async fn comment_reaction(args: Value, client: &OutlineClient, add: bool) -> Result<Value> {
+    let id = get_string_arg(&args, "id")?;
+    let emoji = get_string_arg(&args, "emoji")?;
+    let (endpoint, message) = if add {
+        ("comments.add_reaction", "Reaction added successfully")
+    } else {
+        ("comments.remove_reaction", "Reaction removed successfully")
+    };
+
+    debug!("Calling {} for comment {}: {}", endpoint, id, emoji);
+
+    let request_body = json!({
+        "id": id,
+        "emoji": emoji
+    });
+    let response = client.post(endpoint, request_body).await?;
+
+    Ok(create_mcp_success_response(message, Some(response)))
+}
+
+async fn set_comment_resolved(
+    args: Value,
+    client: &OutlineClient,
+    resolved: bool,
+) -> Result<Value> {
+    let id = get_string_arg(&args, "id")?;
+    let (endpoint, message) = if resolved {
+        ("comments.resolve", "Comment resolved successfully")
+    } else {
+        ("comments.unresolve", "Comment reopened successfully")
+    };
+
+    debug!("Calling {} for comment: {}", endpoint, id);
+
+    let response = client.post(endpoint, json!({ "id": id })).await?;
+
+    Ok(create_mcp_success_response(message, Some(response)))
+}
+
 async fn list_document_comments(args: Value, client: &OutlineClient) -> Result<Value> {
     let document_id = get_string_arg(&args, "document_id")?;
     let limit = get_optional_number_arg(&args, "limit");
+    let status = ThreadStatus::from_args(&args, "list_document_comments")?;
+    let threaded = get_optional_bool_arg(&args, "threaded").unwrap_or(false);
 
     debug!("Listing comments for document: {}", document_id);
 
-    let mut request_body = json!({ "documentId": document_id });
-    if let Some(lim) = limit {
-        request_body["limit"] = json!(lim);
+    if !threaded && status == ThreadStatus::All {
+        let mut request_body = json!({
+            "documentId": document_id,
+            "includeAnchorText": true
+        });
+        if let Some(lim) = limit {
+            request_body["limit"] = json!(lim);
+        }
+
+        let response = client.post("comments.list", request_body).await?;
+        return Ok(create_mcp_success_response(
+            "Comments listed successfully",
+            Some(response),
+        ));
     }
 
-    let response = client.post("comments.list", request_body).await?;
+    // Threads and their status span pages, so filter the whole discussion
+    // and apply the limit afterwards
+    let threads = status.filter(group_threads(
+        fetch_all_comments(client, &document_id).await?,
+    ));
+    let mut data: Vec<Value> = if threaded {
+        threads
+    } else {
+        // Keep replies of matching threads so the listing stays consistent
+        threads
+            .into_iter()
+            .flat_map(|mut thread| {
+                let replies = thread["replies"].as_array().cloned().unwrap_or_default();
+                if let Some(obj) = thread.as_object_mut() {
+                    obj.remove("replies");
+                }
+                std::iter::once(thread).chain(replies)
+            })
+            .collect()
+    };
+    if let Some(lim) = limit.and_then(|l| usize::try_from(l).ok()) {
+        data.truncate(lim);
+    }
+    let response = json!({ "data": data });
 
     Ok(create_mcp_success_response(
         "Comments listed successfully",
@@ -131,12 +333,26 @@ async fn list_document_comments(args: Value, client: &OutlineClient) -> Result<V
     ))
 }
 
+async fn get_document_discussion(args: Value, client: &OutlineClient) -> Result<Value> {
+    let document_id = get_string_arg(&args, "document_id")?;
+    let status = ThreadStatus::from_args(&args, "get_document_discussion")?;
+
+    debug!("Rendering discussion for document: {}", document_id);
+
+    let comments = fetch_all_comments(client, &document_id).await?;
+    let threads = status.filter(group_threads(comments));
+    Ok(create_mcp_success_response(
+        &render_discussion(&threads),
+        None,
+    ))
+}
+
 async fn get_comment(args: Value, client: &OutlineClient) -> Result<Value> {
     let id = get_string_arg(&args, "id")?;
 
     debug!("Getting comment: {}", id);
 
-    let request_body = json!({ "id": id });
+    let request_body = json!({ "id": id, "includeAnchorText": true });
     let response = client.post("comments.info", request_body).await?;
 
     Ok(create_mcp_success_response(
@@ -144,3 +360,357 @@ async fn get_comment(args: Value, client: &OutlineClient) -> Result<Value> {
         Some(response),
     ))
 }
+
+/// Fetch every comment of a document, page by page
+async fn fetch_all_comments(client: &OutlineClient, document_id: &str) -> Result<Vec<Value>> {
+    let mut comments = Vec::new();
+    loop {
+        let response = client
+            .post(
+                "comments.list",
+                json!({
+                    "documentId": document_id,
+                    "includeAnchorText": true,
+                    "limit": COMMENTS_PAGE_SIZE,
+                    "offset": comments.len()
+                }),
+            )
+            .await?;
+        let page = response["data"].as_array().cloned().unwrap_or_default();
+        let page_len = page.len();
+        comments.extend(page);
+        if page_len < COMMENTS_PAGE_SIZE {
+            return Ok(comments);
+        }
+    }
+}
+
+/// Comment body supplied by the caller
+enum CommentContent {
+    Markdown(String),
+    ProseMirror(Value),
+}
+
+fn comment_content(args: &Value, tool_name: &str) -> Result<CommentContent> {
+    if let Some(text) = get_optional_string_arg(args, "text") {
+        return Ok(CommentContent::Markdown(text));
+    }
+
+    match args.get("data") {
+        // Older callers passed markdown in `data`
+        Some(Value::String(text)) => Ok(CommentContent::Markdown(text.clone())),
+        Some(data @ Value::Object(_)) => Ok(CommentContent::ProseMirror(data.clone())),
+        _ => Err(Error::Tool {
+            tool_name: tool_name.to_string(),
+            message: "Missing required parameter 'text'".to_string(),
+            source: None,
+        }),
+    }
+}
+
+/// Resolution filter applied to whole threads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ThreadStatus {
+    All,
+    Resolved,
+    Unresolved,
+}
+
+impl ThreadStatus {
+    fn from_args(args: &Value, tool_name: &str) -> Result<Self> {
+        match get_optional_string_arg(args, "status").as_deref() {
+            None | Some("all") => Ok(Self::All),
+            Some("resolved") => Ok(Self::Resolved),
+            Some("unresolved") => Ok(Self::Unresolved),
+            Some(other) => Err(Error::Tool {
+                tool_name: tool_name.to_string(),
+                message: format!("Invalid status '{other}', expected resolved, unresolved or all"),
+                source: None,
+            }),
+        }
+    }
+
+    fn filter(self, threads: Vec<Value>) -> Vec<Value> {
+        threads
+            .into_iter()
+            .filter(|thread| match self {
+                Self::All => true,
+                Self::Resolved => is_resolved(thread),
+                Self::Unresolved => !is_resolved(thread),
+            })
+            .collect()
+    }
+}
+
+fn is_resolved(comment: &Value) -> bool {
+    comment["resolvedAt"].as_str().is_some()
+}
+
+/// Nest replies under their top-level comment as `replies`, oldest first
+///
+/// Replies whose parent is missing from the list are treated as threads of
+/// their own so nothing is dropped.
+fn group_threads(mut comments: Vec<Value>) -> Vec<Value> {
+    comments.sort_by(|a, b| {
+        a["createdAt"]
+            .as_str()
+            .unwrap_or_default()
+            .cmp(b["createdAt"].as_str().unwrap_or_default())
+    });
+
+    let ids: Vec<&str> = comments.iter().filter_map(|c| c["id"].as_str()).collect();
+    let is_reply = |comment: &Value| {
+        comment["parentCommentId"]
+            .as_str()
+            .is_some_and(|parent| ids.contains(&parent))
+    };
+
+    let mut replies: HashMap<String, Vec<Value>> = HashMap::new();
+    let mut threads = Vec::new();
+    for comment in &comments {
+        if is_reply(comment) {
+            let parent = comment["parentCommentId"].as_str().unwrap_or_default();
+            replies
+                .entry(parent.to_string())
+                .or_default()
+                .push(comment.clone());
+        } else {
+            threads.push(comment.clone());
+        }
+    }
+
+    for thread in &mut threads {
+        let id = thread["id"].as_str().unwrap_or_default().to_string();
+        thread["replies"] = json!(replies.remove(&id).unwrap_or_default());
+    }
+
+    threads
+}
+
+/// Render grouped threads as a markdown discussion
+fn render_discussion(threads: &[Value]) -> String {
+    if threads.is_empty() {
+        return "No comments.".to_string();
+    }
+
+    let resolved = threads.iter().filter(|t| is_resolved(t)).count();
+    let mut output = format!(
+        "# Discussion ({} threads, {resolved} resolved)\n",
+        threads.len()
+    );
+
+    for (index, thread) in threads.iter().enumerate() {
+        let state = if is_resolved(thread) {
+            thread["resolvedBy"]["name"].as_str().map_or_else(
+                || "resolved".to_string(),
+                |name| format!("resolved by {name}"),
+            )
+        } else {
+            "open".to_string()
+        };
+        let _ = writeln!(
+            output,
+            "\n## Thread {} ({state}) `{}`",
+            index + 1,
+            thread["id"].as_str().unwrap_or_default()
+        );
+
+        if let Some(anchor) = thread["anchorText"].as_str().filter(|a| !a.is_empty()) {
+            for line in anchor.lines() {
+                let _ = writeln!(output, "> {line}");
+            }
+            output.push('\n');
+        }
+
+        render_comment(&mut output, thread, "");
+        for reply in thread["replies"].as_array().into_iter().flatten() {
+            render_comment(&mut output, reply, "  ");
+        }
+    }
+
+    output
+}
+
+fn render_comment(output: &mut String, comment: &Value, indent: &str) {
+    let author = comment["createdBy"]["name"].as_str().unwrap_or("Unknown");
+    let created = comment["createdAt"].as_str().unwrap_or_default();
+    let text = comment_text(comment);
+    let mut lines = text.lines();
+
+    let _ = writeln!(
+        output,
+        "{indent}- **{author}** ({created}): {}",
+        lines.next().unwrap_or_default()
+    );
+    for line in lines {
+        let _ = writeln!(output, "{indent}  {line}");
+    }
+}
+
+/// Plain text of a comment, from markdown `text` or `ProseMirror` `data`
+pub(super) fn comment_text(comment: &Value) -> String {
+    if let Some(text) = comment["text"].as_str() {
+        return text.trim().to_string();
+    }
+
+    let mut blocks = Vec::new();
+    for block in comment["data"]["content"].as_array().into_iter().flatten() {
+        let mut text = String::new();
+        collect_text(block, &mut text);
+        blocks.push(text);
+    }
+    blocks.join("\n").trim().to_string()
+}
+
+fn collect_text(node: &Value, output: &mut String) {
+    match node["type"].as_str() {
+        Some("text") => output.push_str(node["text"].as_str().unwrap_or_default()),
+        Some("mention") => {
+            let _ = write!(
+                output,
+                "@{}",
+                node["attrs"]["label"].as_str().unwrap_or_default()
+            );
+        }
+        Some("hard_break" | "br") => output.push('\n'),
+        _ => {
+            for (index, child) in node["content"].as_array().into_iter().flatten().enumerate() {
+                // Nested blocks such as list items go on their own lines
+                if index > 0 && child["content"].is_array() {
+                    output.push('\n');
+                }
+                collect_text(child, output);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::stand_in;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_create_comment_requires_only_document() {
+        let tool = &get_comment_tools()[0];
+        assert_eq!(tool["inputSchema"]["required"], json!(["document_id"]));
+    }
+
+    #[tokio::test]
+    async fn test_status_filter_looks_past_first_page() {
+        let client = stand_in::serve(vec![(
+            "comments.list",
+            Arc::new(|body: &Value| {
+                let page: Vec<Value> = if body["offset"].as_u64() == Some(0) {
+                    (0..COMMENTS_PAGE_SIZE)
+                        .map(|i| {
+                            json!({
+                                "id": format!("c{i}"),
+                                "createdAt": format!("2024-01-01T00:{:02}:{:02}Z", i / 60, i % 60),
+                                "resolvedAt": "2024-02-01T00:00:00Z"
+                            })
+                        })
+                        .collect()
+                } else {
+                    vec![json!({ "id": "open", "createdAt": "2024-03-01T00:00:00Z" })]
+                };
+                stand_in::ok(&json!({ "data": page }))
+            }),
+        )])
+        .await;
+
+        let args = json!({ "document_id": "doc-1", "status": "unresolved", "limit": 25 });
+        let response = list_document_comments(args, &client).await.unwrap();
+        let data = &response["structuredContent"]["data"];
+        assert_eq!(data.as_array().unwrap().len(), 1);
+        assert_eq!(data[0]["id"], "open");
+    }
+
+    fn comment(id: &str, parent: Option<&str>, created: &str, text: &str) -> Value {
+        json!({
+            "id": id,
+            "parentCommentId": parent,
+            "createdAt": created,
+            "createdBy": { "name": format!("user-{id}") },
+            "data": {
+                "type": "doc",
+                "content": [{
+                    "type": "paragraph",
+                    "content": [{ "type": "text", "text": text }]
+                }]
+            }
+        })
+    }
+
+    #[test]
+    fn test_group_threads() {
+        let comments = vec![
+            comment("r2", Some("t1"), "2024-01-03", "second reply"),
+            comment("t1", None, "2024-01-01", "question"),
+            comment("r1", Some("t1"), "2024-01-02", "first reply"),
+            comment("o1", Some("gone"), "2024-01-04", "orphan"),
+        ];
+
+        let threads = group_threads(comments);
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0]["id"], "t1");
+        assert_eq!(threads[0]["replies"][0]["id"], "r1");
+        assert_eq!(threads[0]["replies"][1]["id"], "r2");
+        assert_eq!(threads[1]["id"], "o1");
+    }
+
+    #[test]
+    fn test_thread_status_filter() {
+        let mut resolved = comment("t1", None, "2024-01-01", "done");
+        resolved["resolvedAt"] = json!("2024-01-05");
+        let open = comment("t2", None, "2024-01-02", "open");
+        let threads = vec![resolved, open];
+
+        assert_eq!(ThreadStatus::All.filter(threads.clone()).len(), 2);
+        assert_eq!(
+            ThreadStatus::Resolved.filter(threads.clone())[0]["id"],
+            "t1"
+        );
+        assert_eq!(ThreadStatus::Unresolved.filter(threads)[0]["id"], "t2");
+        assert!(ThreadStatus::from_args(&json!({ "status": "closed" }), "t").is_err());
+    }
+
+    #[test]
+    fn test_comment_text() {
+        let data = json!({
+            "data": {
+                "type": "doc",
+                "content": [
+                    {
+                        "type": "paragraph",
+                        "content": [
+                            { "type": "text", "text": "Hi " },
+                            { "type": "mention", "attrs": { "label": "Ann" } }
+                        ]
+                    },
+                    { "type": "paragraph", "content": [{ "type": "text", "text": "Thanks" }] }
+                ]
+            }
+        });
+        assert_eq!(comment_text(&data), "Hi @Ann\nThanks");
+        assert_eq!(comment_text(&json!({ "text": " *md* " })), "*md*");
+    }
+
+    #[test]
+    fn test_render_discussion() {
+        let mut thread = comment("t1", None, "2024-01-01", "Is this right?");
+        thread["anchorText"] = json!("the quoted part");
+        thread["resolvedAt"] = json!("2024-01-03");
+        thread["resolvedBy"] = json!({ "name": "Ann" });
+        let threads = group_threads(vec![thread, comment("r1", Some("t1"), "2024-01-02", "Yes")]);
+
+        let markdown = render_discussion(&threads);
+        assert!(markdown.starts_with("# Discussion (1 threads, 1 resolved)"));
+        assert!(markdown.contains("## Thread 1 (resolved by Ann) `t1`"));
+        assert!(markdown.contains("> the quoted part"));
+        assert!(markdown.contains("- **user-t1** (2024-01-01): Is this right?"));
+        assert!(markdown.contains("  - **user-r1** (2024-01-02): Yes"));
+        assert_eq!(render_discussion(&[]), "No comments.");
+    }
+}
@@ -1,21 +1,15 @@
 //! Common utilities for tool implementations
 
 use crate::error::{Error, Result};
+use crate::mcp::Transport;
 use serde_json::{json, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Create tool definition JSON
 pub fn tool_definition(name: &str, description: &str, params: &[(&str, &str, &str)]) -> Value {
     let properties = params
         .iter()
-        .map(|(name, param_type, desc)| {
-            (
-                (*name).to_string(),
-                json!({
-                    "type": param_type,
-                    "description": desc
-                }),
-            )
-        })
+        .map(|(name, param_type, desc)| ((*name).to_string(), param_schema(param_type, desc)))
         .collect::<serde_json::Map<String, Value>>();
 
     json!({
@@ -29,6 +23,32 @@ pub fn tool_definition(name: &str, description: &str, params: &[(&str, &str, &st
     })
 }
 
+/// Add an optional parameter to an existing tool definition
+pub fn add_optional_param(tool: &mut Value, (name, param_type, description): (&str, &str, &str)) {
+    tool["inputSchema"]["properties"][name] = param_schema(param_type, description);
+}
+
+/// JSON schema of one parameter; arrays are lists of strings unless a tool
+/// overrides `items`
+fn param_schema(param_type: &str, description: &str) -> Value {
+    let mut schema = json!({
+        "type": param_type,
+        "description": description
+    });
+    if param_type == "array" {
+        schema["items"] = json!({ "type": "string" });
+    }
+    schema
+}
+
+/// Add optional parameters to a tool definition built with [`tool_definition`]
+pub fn with_optional_params(mut tool: Value, params: &[(&str, &str, &str)]) -> Value {
+    for param in params {
+        add_optional_param(&mut tool, *param);
+    }
+    tool
+}
+
 /// Extract string argument from JSON arguments
 pub fn get_string_arg(args: &Value, name: &str) -> Result<String> {
     args.get(name)
@@ -53,6 +73,44 @@ pub fn get_optional_number_arg(args: &Value, name: &str) -> Option<i64> {
     args.get(name).and_then(serde_json::Value::as_i64)
 }
 
+/// Extract optional boolean argument from JSON arguments
+pub fn get_optional_bool_arg(args: &Value, name: &str) -> Option<bool> {
+    args.get(name).and_then(serde_json::Value::as_bool)
+}
+
+/// Extract optional array of strings from JSON arguments
+///
+/// A single string is accepted as a one-element array.
+pub fn get_optional_string_array_arg(args: &Value, name: &str) -> Option<Vec<String>> {
+    match args.get(name)? {
+        Value::String(s) => Some(vec![s.clone()]),
+        Value::Array(items) => Some(
+            items
+                .iter()
+                .filter_map(|v| v.as_str().map(ToString::to_string))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Reject arguments that access the local filesystem outside of STDIO mode
+pub fn ensure_local_files_allowed(
+    transport: Transport,
+    tool_name: &str,
+    arg_name: &str,
+) -> Result<()> {
+    if transport.allows_local_files() {
+        Ok(())
+    } else {
+        Err(Error::Tool {
+            tool_name: tool_name.to_string(),
+            message: format!("'{arg_name}' is only supported in STDIO mode"),
+            source: None,
+        })
+    }
+}
+
 /// Create MCP-compliant success response with structured content
 pub fn create_mcp_success_response(message: &str, structured_content: Option<Value>) -> Value {
     let text = structured_content.as_ref().map_or_else(
@@ -75,6 +133,41 @@ pub fn create_mcp_success_response(message: &str, structured_content: Option<Val
     result
 }
 
+/// Create MCP-compliant success response with an embedded binary resource
+pub fn create_mcp_resource_response(
+    message: &str,
+    uri: &str,
+    mime_type: &str,
+    data: &[u8],
+    structured_content: Option<Value>,
+) -> Value {
+    use base64::Engine;
+
+    let mut result = json!({
+        "content": [
+            {
+                "type": "text",
+                "text": message
+            },
+            {
+                "type": "resource",
+                "resource": {
+                    "uri": uri,
+                    "mimeType": mime_type,
+                    "blob": base64::engine::general_purpose::STANDARD.encode(data)
+                }
+            }
+        ],
+        "isError": false
+    });
+
+    if let Some(content) = structured_content {
+        result["structuredContent"] = content;
+    }
+
+    result
+}
+
 /// Create MCP-compliant error response
 pub fn create_mcp_error_response(error_message: &str) -> Value {
     json!({
@@ -86,6 +179,40 @@ pub fn create_mcp_error_response(error_message: &str) -> Value {
     })
 }
 
+/// Current time as seconds since the Unix epoch
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Format Unix seconds as an ISO 8601 UTC timestamp in Outline's format
+pub fn iso_timestamp(unix_secs: u64) -> String {
+    let (year, month, day) = civil_from_days(unix_secs / 86_400);
+    let secs_of_day = unix_secs % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.000Z",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) civil date
+pub const fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Howard Hinnant's algorithm, shifted so the era starts on 0000-03-01
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
 /// Handle tool error and return MCP-compliant error response
 pub fn handle_tool_error(error: &crate::error::Error) -> Value {
     create_mcp_error_response(&error.to_string())
@@ -118,6 +245,58 @@ mod tests {
         assert_eq!(get_optional_string_arg(&args, "missing"), None);
     }
 
+    #[test]
+    fn test_get_optional_bool_arg() {
+        let args = json!({
+            "publish": true,
+            "title": "Test Title"
+        });
+
+        assert_eq!(get_optional_bool_arg(&args, "publish"), Some(true));
+        assert_eq!(get_optional_bool_arg(&args, "title"), None);
+        assert_eq!(get_optional_bool_arg(&args, "missing"), None);
+    }
+
+    #[test]
+    fn test_get_optional_string_array_arg() {
+        let args = json!({
+            "paths": ["a.md", "b.md", 3],
+            "path": "c.md"
+        });
+
+        assert_eq!(
+            get_optional_string_array_arg(&args, "paths"),
+            Some(vec!["a.md".to_string(), "b.md".to_string()])
+        );
+        assert_eq!(
+            get_optional_string_array_arg(&args, "path"),
+            Some(vec!["c.md".to_string()])
+        );
+        assert_eq!(get_optional_string_array_arg(&args, "missing"), None);
+    }
+
+    #[test]
+    fn test_ensure_local_files_allowed() {
+        assert!(ensure_local_files_allowed(Transport::Stdio, "tool", "path").is_ok());
+        assert!(ensure_local_files_allowed(Transport::Http, "tool", "path").is_err());
+    }
+
+    #[test]
+    fn test_create_mcp_resource_response() {
+        let response = create_mcp_resource_response(
+            "Exported",
+            "outline://test",
+            "application/zip",
+            b"hello",
+            None,
+        );
+
+        assert_eq!(response["isError"], false);
+        assert_eq!(response["content"][0]["text"], "Exported");
+        assert_eq!(response["content"][1]["resource"]["blob"], "aGVsbG8=");
+        assert!(response.get("structuredContent").is_none());
+    }
+
     #[test]
     fn test_tool_definition() {
         let tool = tool_definition(
@@ -130,4 +309,46 @@ mod tests {
         assert_eq!(tool["description"], "Test description");
         assert!(tool["inputSchema"]["properties"]["param1"].is_object());
     }
+
+    #[test]
+    fn test_add_optional_param() {
+        let mut tool = tool_definition("test_tool", "Test", &[("id", "string", "ID")]);
+        add_optional_param(&mut tool, ("limit", "number", "Limit (optional)"));
+
+        assert_eq!(tool["inputSchema"]["properties"]["limit"]["type"], "number");
+        assert_eq!(tool["inputSchema"]["required"], json!(["id"]));
+
+        let tool = with_optional_params(
+            tool_definition("test_tool", "Test", &[]),
+            &[
+                ("a", "string", "A (optional)"),
+                ("b", "number", "B (optional)"),
+            ],
+        );
+        assert_eq!(tool["inputSchema"]["required"], json!([]));
+        assert!(tool["inputSchema"]["properties"]["b"].is_object());
+    }
+
+    #[test]
+    fn test_array_params_declare_items() {
+        let mut tool = tool_definition("test_tool", "Test", &[("ids", "array", "IDs")]);
+        add_optional_param(&mut tool, ("fields", "array", "Fields (optional)"));
+
+        let properties = &tool["inputSchema"]["properties"];
+        assert_eq!(properties["ids"]["items"], json!({ "type": "string" }));
+        assert_eq!(properties["fields"]["items"], json!({ "type": "string" }));
+    }
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+
+    #[test]
+    fn test_iso_timestamp() {
+        assert_eq!(iso_timestamp(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(iso_timestamp(1_709_214_305), "2024-02-29T13:45:05.000Z");
+    }
 }
@@ -3,26 +3,54 @@
 use serde_json::{json, Value};
 use tracing::debug;
 
+use super::chunks::{split_by_bytes, split_by_headings, Chunk};
 use super::common::{
-    create_mcp_success_response, get_optional_number_arg, get_optional_string_arg, get_string_arg,
-    tool_definition,
+    add_optional_param, create_mcp_success_response, get_optional_bool_arg,
+    get_optional_number_arg, get_optional_string_arg, get_string_arg, tool_definition,
+    with_optional_params,
 };
-use crate::error::Result;
+use super::mentions::{resolve_mentions_in_args, MENTIONS_PARAM};
+use super::navigation::find_navigation_path;
+use crate::error::{Error, Result};
 use crate::outline::{
     create_document_request, search_documents_request, update_document_request,
     Client as OutlineClient,
 };
 
+/// Snippets returned when `ask_workspace` falls back to search
+const DEFAULT_ANSWER_SNIPPETS: i64 = 5;
+
+/// Default chunk size of `get_document_chunk` in bytes
+const DEFAULT_CHUNK_SIZE: usize = 20_000;
+
 /// Get all document tool definitions
+#[allow(clippy::too_many_lines)]
 pub fn get_document_tools() -> Vec<Value> {
     vec![
-        tool_definition(
-            "create_document",
-            "Create new document",
+        with_optional_params(
+            tool_definition(
+                "create_document",
+                "Create new document",
+                &[
+                    ("title", "string", "Document title"),
+                    ("text", "string", "Document content"),
+                ],
+            ),
             &[
-                ("title", "string", "Document title"),
-                ("text", "string", "Document content"),
                 ("collection_id", "string", "Collection ID (optional)"),
+                (
+                    "parent_document_id",
+                    "string",
+                    "Parent document ID to nest under (optional)",
+                ),
+                ("index", "number", "Position among siblings (optional)"),
+                (
+                    "publish",
+                    "boolean",
+                    "Publish immediately instead of creating a draft (optional)",
+                ),
+                ("template", "boolean", "Create as a template (optional)"),
+                MENTIONS_PARAM,
             ],
         ),
         tool_definition(
@@ -30,15 +58,74 @@ pub fn get_document_tools() -> Vec<Value> {
             "Get document by ID",
             &[("id", "string", "Document ID")],
         ),
-        tool_definition(
-            "update_document",
-            "Update document",
+        with_optional_params(
+            tool_definition(
+                "get_document_chunk",
+                "Get one numbered chunk of a long document, split at headings or by size",
+                &[("id", "string", "Document ID")],
+            ),
+            &[
+                (
+                    "chunk",
+                    "number",
+                    "Chunk number starting at 1, default 1 (optional)",
+                ),
+                (
+                    "split_by",
+                    "string",
+                    "headings (sections at # and ## headings) or bytes, default headings (optional)",
+                ),
+                (
+                    "chunk_size",
+                    "number",
+                    "Maximum chunk size in bytes, default 20000 (optional)",
+                ),
+            ],
+        ),
+        with_optional_params(
+            tool_definition(
+                "update_document",
+                "Update document",
+                &[("id", "string", "Document ID")],
+            ),
             &[
-                ("id", "string", "Document ID"),
                 ("title", "string", "New title (optional)"),
                 ("text", "string", "New content (optional)"),
+                (
+                    "append",
+                    "boolean",
+                    "Append text to the end instead of replacing the content (optional)",
+                ),
+                ("icon", "string", "Icon name or emoji (optional)"),
+                (
+                    "emoji",
+                    "string",
+                    "Emoji, for Outline versions without icons (optional)",
+                ),
+                (
+                    "color",
+                    "string",
+                    "Icon color as hex, e.g. #FF5C80 (optional)",
+                ),
+                (
+                    "full_width",
+                    "boolean",
+                    "Display document in full width (optional)",
+                ),
+                ("publish", "boolean", "Publish a draft (optional)"),
+                (
+                    "done",
+                    "boolean",
+                    "End the editing session so the change is recorded as a revision (optional)",
+                ),
+                MENTIONS_PARAM,
             ],
         ),
+        tool_definition(
+            "unpublish_document",
+            "Unpublish document, turning it back into a draft",
+            &[("id", "string", "Document ID")],
+        ),
         tool_definition(
             "delete_document",
             "Delete document",
@@ -60,19 +147,29 @@ pub fn get_document_tools() -> Vec<Value> {
                 ("limit", "number", "Number of results (optional)"),
             ],
         ),
+        tool_definition(
+            "ask_workspace",
+            "Answer a question from workspace documents with source references, falls back to search snippets",
+            &[
+                ("query", "string", "Question in natural language"),
+                (
+                    "collection_id",
+                    "string",
+                    "Only use documents of this collection (optional)",
+                ),
+                (
+                    "limit",
+                    "number",
+                    "Snippets returned by the search fallback, default 5 (optional)",
+                ),
+            ],
+        ),
         tool_definition(
             "archive_document",
             "Archive document",
             &[("id", "string", "Document ID")],
         ),
-        tool_definition(
-            "move_document",
-            "Move document",
-            &[
-                ("id", "string", "Document ID"),
-                ("collection_id", "string", "Target collection ID"),
-            ],
-        ),
+        move_document_tool(),
         tool_definition(
             "create_template_from_document",
             "Create template from document",
@@ -106,9 +203,52 @@ pub fn get_document_tools() -> Vec<Value> {
                 ("limit", "number", "Number of drafts (optional)"),
             ],
         ),
+        tool_definition(
+            "list_child_documents",
+            "List direct children of a document",
+            &[
+                ("id", "string", "Parent document ID"),
+                ("limit", "number", "Number of documents (optional)"),
+            ],
+        ),
+        tool_definition(
+            "get_document_path",
+            "Get breadcrumb path from collection root to document",
+            &[("id", "string", "Document ID")],
+        ),
     ]
 }
 
+/// `move_document` definition, where only the document ID is required
+fn move_document_tool() -> Value {
+    let mut tool = tool_definition(
+        "move_document",
+        "Move document",
+        &[("id", "string", "Document ID")],
+    );
+    add_optional_param(
+        &mut tool,
+        (
+            "collection_id",
+            "string",
+            "Target collection ID (optional if parent_document_id is set)",
+        ),
+    );
+    add_optional_param(
+        &mut tool,
+        (
+            "parent_document_id",
+            "string",
+            "New parent document ID (optional)",
+        ),
+    );
+    add_optional_param(
+        &mut tool,
+        ("index", "number", "Position among siblings (optional)"),
+    );
+    tool
+}
+
 /// Call document tool
 pub async fn call_document_tool(
     name: &str,
@@ -118,16 +258,21 @@ pub async fn call_document_tool(
     match name {
         "create_document" => create_document(arguments, client).await,
         "get_document" => get_document(arguments, client).await,
+        "get_document_chunk" => get_document_chunk(arguments, client).await,
         "update_document" => update_document(arguments, client).await,
+        "unpublish_document" => unpublish_document(arguments, client).await,
         "delete_document" => delete_document(arguments, client).await,
         "list_documents" => list_documents(arguments, client).await,
         "search_documents" => search_documents(arguments, client).await,
+        "ask_workspace" => ask_workspace(arguments, client).await,
         "archive_document" => archive_document(arguments, client).await,
         "move_document" => move_document(arguments, client).await,
         "create_template_from_document" => create_template_from_document(arguments, client).await,
         "restore_document" => restore_document(arguments, client).await,
         "unarchive_document" => unarchive_document(arguments, client).await,
         "list_drafts" => list_drafts(arguments, client).await,
+        "list_child_documents" => list_child_documents(arguments, client).await,
+        "get_document_path" => get_document_path(arguments, client).await,
         _ => unreachable!("Unknown document tool: {}", name),
     }
 }
@@ -136,11 +281,32 @@ async fn create_document(args: Value, client: &OutlineClient) -> Result<Value> {
     let title = get_string_arg(&args, "title")?;
     let text = get_string_arg(&args, "text")?;
     let collection_id = get_optional_string_arg(&args, "collection_id");
+    let parent_document_id = get_optional_string_arg(&args, "parent_document_id");
+    let index = get_optional_number_arg(&args, "index");
+    let publish = get_optional_bool_arg(&args, "publish");
+    let template = get_optional_bool_arg(&args, "template");
 
     debug!("Creating document: {}", title);
 
-    let request_body = create_document_request(&title, &text, collection_id.as_deref());
-    let response = client.post("documents.create", request_body).await?;
+    let (text, mentions) = resolve_mentions_in_args(&args, client, text).await;
+    let mut request_body = create_document_request(&title, &text, collection_id.as_deref());
+    if let Some(pid) = parent_document_id {
+        request_body["parentDocumentId"] = json!(pid);
+    }
+    if let Some(idx) = index {
+        request_body["index"] = json!(idx);
+    }
+    if let Some(p) = publish {
+        request_body["publish"] = json!(p);
+    }
+    if let Some(t) = template {
+        request_body["template"] = json!(t);
+    }
+
+    let mut response = client.post("documents.create", request_body).await?;
+    if let Some(mentions) = mentions {
+        mentions.annotate(&mut response);
+    }
 
     Ok(create_mcp_success_response(
         "Document created successfully",
@@ -162,15 +328,134 @@ async fn get_document(args: Value, client: &OutlineClient) -> Result<Value> {
     ))
 }
 
+async fn get_document_chunk(args: Value, client: &OutlineClient) -> Result<Value> {
+    let id = get_string_arg(&args, "id")?;
+    let number = get_optional_number_arg(&args, "chunk")
+        .and_then(|n| usize::try_from(n).ok())
+        .unwrap_or(1);
+    let chunk_size = get_optional_number_arg(&args, "chunk_size")
+        .and_then(|n| usize::try_from(n).ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_CHUNK_SIZE);
+    let split_by = get_optional_string_arg(&args, "split_by").unwrap_or_else(|| "headings".into());
+
+    debug!(
+        "Getting chunk {} of document {} by {}",
+        number, id, split_by
+    );
+
+    let response = client.post("documents.info", json!({ "id": id })).await?;
+    let document = &response["data"];
+    let text = document["text"].as_str().unwrap_or_default();
+
+    let chunks: Vec<Chunk> = match split_by.as_str() {
+        "headings" => split_by_headings(text, chunk_size),
+        "bytes" => split_by_bytes(text, chunk_size)
+            .into_iter()
+            .map(|range| Chunk {
+                range,
+                heading: None,
+            })
+            .collect(),
+        other => {
+            return Err(Error::Tool {
+                tool_name: "get_document_chunk".to_string(),
+                message: format!("Invalid split_by '{other}', expected headings or bytes"),
+                source: None,
+            })
+        }
+    };
+
+    let Some(chunk) = number.checked_sub(1).and_then(|index| chunks.get(index)) else {
+        return Err(Error::Tool {
+            tool_name: "get_document_chunk".to_string(),
+            message: format!(
+                "Chunk {number} does not exist, document has {} chunks",
+                chunks.len()
+            ),
+            source: None,
+        });
+    };
+
+    let mut result = json!({
+        "chunk": number,
+        "totalChunks": chunks.len(),
+        "splitBy": split_by,
+        "bytes": format!("{}-{} of {}", chunk.range.start, chunk.range.end, text.len()),
+        "nextChunk": (number < chunks.len()).then_some(number + 1),
+        "data": {
+            "id": document["id"],
+            "title": document["title"],
+            "url": document["url"],
+            "updatedAt": document["updatedAt"],
+            "text": &text[chunk.range.clone()]
+        }
+    });
+    if split_by == "headings" {
+        let sections: Vec<String> = chunks
+            .iter()
+            .enumerate()
+            .map(|(index, c)| {
+                format!(
+                    "{}. {}",
+                    index + 1,
+                    c.heading.as_deref().unwrap_or("(start)")
+                )
+            })
+            .collect();
+        result["sections"] = json!(sections);
+    }
+
+    Ok(create_mcp_success_response(
+        "Document chunk retrieved successfully",
+        Some(result),
+    ))
+}
+
 async fn update_document(args: Value, client: &OutlineClient) -> Result<Value> {
     let id = get_string_arg(&args, "id")?;
     let title = get_optional_string_arg(&args, "title");
     let text = get_optional_string_arg(&args, "text");
+    let append = get_optional_bool_arg(&args, "append");
+
+    if append == Some(true) && text.is_none() {
+        return Err(Error::Tool {
+            tool_name: "update_document".to_string(),
+            message: "'append' requires 'text'".to_string(),
+            source: None,
+        });
+    }
 
     debug!("Updating document: {}", id);
 
-    let request_body = update_document_request(&id, title.as_deref(), text.as_deref());
-    let response = client.post("documents.update", request_body).await?;
+    let (text, mentions) = match text {
+        Some(text) => {
+            let (text, mentions) = resolve_mentions_in_args(&args, client, text).await;
+            (Some(text), mentions)
+        }
+        None => (None, None),
+    };
+    let mut request_body = update_document_request(&id, title.as_deref(), text.as_deref());
+    for (arg, field) in [("icon", "icon"), ("emoji", "emoji"), ("color", "color")] {
+        if let Some(value) = get_optional_string_arg(&args, arg) {
+            request_body[field] = json!(value);
+        }
+    }
+    for (arg, field) in [
+        ("append", "append"),
+        ("full_width", "fullWidth"),
+        ("publish", "publish"),
+        ("done", "done"),
+    ] {
+        if let Some(value) = get_optional_bool_arg(&args, arg) {
+            request_body[field] = json!(value);
+        }
+    }
+
+    let mut response = client.post("documents.update", request_body).await?;
+    if let Some(mentions) = mentions {
+        mentions.annotate(&mut response);
+    }
 
     Ok(create_mcp_success_response(
         "Document updated successfully",
@@ -178,6 +463,20 @@ async fn update_document(args: Value, client: &OutlineClient) -> Result<Value> {
     ))
 }
 
+async fn unpublish_document(args: Value, client: &OutlineClient) -> Result<Value> {
+    let id = get_string_arg(&args, "id")?;
+
+    debug!("Unpublishing document: {}", id);
+
+    let request_body = json!({ "id": id });
+    let response = client.post("documents.unpublish", request_body).await?;
+
+    Ok(create_mcp_success_response(
+        "Document unpublished successfully",
+        Some(response),
+    ))
+}
+
 async fn delete_document(args: Value, client: &OutlineClient) -> Result<Value> {
     let id = get_string_arg(&args, "id")?;
 
@@ -228,6 +527,42 @@ async fn search_documents(args: Value, client: &OutlineClient) -> Result<Value>
     ))
 }
 
+async fn ask_workspace(args: Value, client: &OutlineClient) -> Result<Value> {
+    let query = get_string_arg(&args, "query")?;
+    let collection_id = get_optional_string_arg(&args, "collection_id");
+    let limit = get_optional_number_arg(&args, "limit").unwrap_or(DEFAULT_ANSWER_SNIPPETS);
+
+    debug!("Asking workspace: {}", query);
+
+    let mut request_body = json!({ "query": query });
+    if let Some(cid) = &collection_id {
+        request_body["collectionId"] = json!(cid);
+    }
+
+    match client.post("documents.answerQuestion", request_body).await {
+        Ok(response) => Ok(create_mcp_success_response(
+            "Question answered successfully",
+            Some(answer_from_response(&response)),
+        )),
+        // AI answers are a plugin and may be missing or disabled on the instance
+        Err(Error::Api { status, .. }) if matches!(status, 403 | 404 | 501) => {
+            debug!("Answers unavailable ({}), falling back to search", status);
+
+            let mut request_body = json!({ "query": query, "limit": limit });
+            if let Some(cid) = collection_id {
+                request_body["collectionId"] = json!(cid);
+            }
+            let response = client.post("documents.search", request_body).await?;
+
+            Ok(create_mcp_success_response(
+                "Answers unavailable, returning search snippets",
+                Some(snippets_from_search(&response)),
+            ))
+        }
+        Err(e) => Err(e),
+    }
+}
+
 async fn archive_document(args: Value, client: &OutlineClient) -> Result<Value> {
     let id = get_string_arg(&args, "id")?;
 
@@ -244,14 +579,34 @@ async fn archive_document(args: Value, client: &OutlineClient) -> Result<Value>
 
 async fn move_document(args: Value, client: &OutlineClient) -> Result<Value> {
     let id = get_string_arg(&args, "id")?;
-    let collection_id = get_string_arg(&args, "collection_id")?;
+    let collection_id = get_optional_string_arg(&args, "collection_id");
+    let parent_document_id = get_optional_string_arg(&args, "parent_document_id");
+    let index = get_optional_number_arg(&args, "index");
+
+    if collection_id.is_none() && parent_document_id.is_none() {
+        return Err(Error::Tool {
+            tool_name: "move_document".to_string(),
+            message: "Either 'collection_id' or 'parent_document_id' is required".to_string(),
+            source: None,
+        });
+    }
 
-    debug!("Moving document {} to collection {}", id, collection_id);
+    debug!(
+        "Moving document {} to collection {:?}, parent {:?}",
+        id, collection_id, parent_document_id
+    );
+
+    let mut request_body = json!({ "id": id });
+    if let Some(cid) = collection_id {
+        request_body["collectionId"] = json!(cid);
+    }
+    if let Some(pid) = parent_document_id {
+        request_body["parentDocumentId"] = json!(pid);
+    }
+    if let Some(idx) = index {
+        request_body["index"] = json!(idx);
+    }
 
-    let request_body = json!({
-        "id": id,
-        "collectionId": collection_id
-    });
     let response = client.post("documents.move", request_body).await?;
 
     Ok(create_mcp_success_response(
@@ -332,3 +687,181 @@ async fn list_drafts(args: Value, client: &OutlineClient) -> Result<Value> {
         Some(response),
     ))
 }
+
+async fn list_child_documents(args: Value, client: &OutlineClient) -> Result<Value> {
+    let id = get_string_arg(&args, "id")?;
+    let limit = get_optional_number_arg(&args, "limit");
+
+    debug!("Listing child documents of: {}", id);
+
+    let mut request_body = json!({ "parentDocumentId": id });
+    if let Some(lim) = limit {
+        request_body["limit"] = json!(lim);
+    }
+
+    let response = client.post("documents.list", request_body).await?;
+
+    Ok(create_mcp_success_response(
+        "Child documents listed successfully",
+        Some(response),
+    ))
+}
+
+async fn get_document_path(args: Value, client: &OutlineClient) -> Result<Value> {
+    let id = get_string_arg(&args, "id")?;
+
+    debug!("Getting document path: {}", id);
+
+    let document = client.post("documents.info", json!({ "id": id })).await?;
+    let document_id = document["data"]["id"].as_str().unwrap_or(&id);
+    let collection_id = document["data"]["collectionId"]
+        .as_str()
+        .ok_or_else(|| Error::Tool {
+            tool_name: "get_document_path".to_string(),
+            message: format!("Document {id} does not belong to a collection"),
+            source: None,
+        })?;
+
+    let navigation = client
+        .post("collections.documents", json!({ "id": collection_id }))
+        .await?;
+    let nodes = navigation["data"].as_array().map_or(&[][..], Vec::as_slice);
+
+    let path = find_navigation_path(nodes, document_id).ok_or_else(|| Error::Tool {
+        tool_name: "get_document_path".to_string(),
+        message: format!(
+            "Document {id} not found in collection structure (drafts are not part of the tree)"
+        ),
+        source: None,
+    })?;
+
+    let breadcrumbs: Vec<Value> = path
+        .iter()
+        .map(|node| {
+            json!({
+                "id": node["id"],
+                "title": node["title"],
+                "url": node["url"]
+            })
+        })
+        .collect();
+
+    Ok(create_mcp_success_response(
+        "Document path retrieved successfully",
+        Some(json!({
+            "collectionId": collection_id,
+            "data": breadcrumbs
+        })),
+    ))
+}
+
+/// Answer and cited documents from a `documents.answerQuestion` response
+fn answer_from_response(response: &Value) -> Value {
+    // Depending on the Outline version the payload may be wrapped in `data`
+    let body = if response["search"].is_null() {
+        &response["data"]
+    } else {
+        response
+    };
+
+    let sources: Vec<Value> = body["documents"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|doc| {
+            json!({
+                "id": doc["id"],
+                "title": doc["title"],
+                "url": doc["url"]
+            })
+        })
+        .collect();
+
+    json!({
+        "mode": "answer",
+        "answer": body["search"]["answer"],
+        "sources": sources
+    })
+}
+
+/// Snippet bundle from a `documents.search` response
+fn snippets_from_search(response: &Value) -> Value {
+    let sources: Vec<Value> = response["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|result| {
+            let context = result["context"].as_str().unwrap_or_default();
+            json!({
+                "id": result["document"]["id"],
+                "title": result["document"]["title"],
+                "url": result["document"]["url"],
+                "snippet": context.replace("<b>", "**").replace("</b>", "**")
+            })
+        })
+        .collect();
+
+    json!({
+        "mode": "search",
+        "answer": null,
+        "sources": sources
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_document_requires_only_id() {
+        let tools = get_document_tools();
+        for name in ["update_document", "get_document_chunk"] {
+            let tool = tools.iter().find(|tool| tool["name"] == name).unwrap();
+            assert_eq!(tool["inputSchema"]["required"], json!(["id"]), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_move_document_requires_only_id() {
+        let tool = move_document_tool();
+        assert_eq!(tool["inputSchema"]["required"], json!(["id"]));
+        for param in ["collection_id", "parent_document_id", "index"] {
+            assert!(tool["inputSchema"]["properties"][param].is_object());
+        }
+    }
+
+    #[test]
+    fn test_answer_from_response() {
+        let response = json!({
+            "documents": [{ "id": "d1", "title": "Onboarding", "url": "/doc/onboarding-abc", "text": "..." }],
+            "search": { "answer": "Ask IT for a laptop." }
+        });
+        let answer = answer_from_response(&response);
+        assert_eq!(answer["mode"], "answer");
+        assert_eq!(answer["answer"], "Ask IT for a laptop.");
+        assert_eq!(
+            answer["sources"][0],
+            json!({ "id": "d1", "title": "Onboarding", "url": "/doc/onboarding-abc" })
+        );
+
+        let wrapped = json!({ "data": response });
+        assert_eq!(answer_from_response(&wrapped), answer);
+    }
+
+    #[test]
+    fn test_snippets_from_search() {
+        let response = json!({
+            "data": [{
+                "context": "request a <b>laptop</b> from IT",
+                "document": { "id": "d1", "title": "Onboarding", "url": "/doc/onboarding-abc" }
+            }]
+        });
+        let snippets = snippets_from_search(&response);
+        assert_eq!(snippets["mode"], "search");
+        assert!(snippets["answer"].is_null());
+        assert_eq!(
+            snippets["sources"][0]["snippet"],
+            "request a **laptop** from IT"
+        );
+    }
+}
@@ -0,0 +1,431 @@
+//! Workspace activity tools
+//!
+//! Raw access to `events.list` and a compact digest of recent changes.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use serde_json::{json, Value};
+use tracing::debug;
+
+use super::common::{
+    create_mcp_success_response, get_optional_bool_arg, get_optional_number_arg,
+    get_optional_string_arg, iso_timestamp, tool_definition, unix_now, with_optional_params,
+};
+use crate::error::Result;
+use crate::outline::Client as OutlineClient;
+
+/// Page size used when collecting events for a digest
+const EVENTS_PAGE_SIZE: usize = 100;
+
+/// Upper bound on events read for one digest
+const MAX_DIGEST_EVENTS: usize = 2000;
+
+/// Documents whose titles are looked up for a digest
+const MAX_TITLE_LOOKUPS: usize = 50;
+
+/// Get all event tool definitions
+pub fn get_event_tools() -> Vec<Value> {
+    vec![
+        with_optional_params(
+            tool_definition("list_events", "List workspace events, newest first", &[]),
+            &[
+                ("actor_id", "string", "Only events by this user (optional)"),
+                (
+                    "document_id",
+                    "string",
+                    "Only events for this document (optional)",
+                ),
+                (
+                    "collection_id",
+                    "string",
+                    "Only events in this collection (optional)",
+                ),
+                (
+                    "name",
+                    "string",
+                    "Event name, e.g. documents.update (optional)",
+                ),
+                (
+                    "audit_log",
+                    "boolean",
+                    "Include audit log events, admin only (optional)",
+                ),
+                ("limit", "number", "Number of events (optional)"),
+                ("offset", "number", "Pagination offset (optional)"),
+            ],
+        ),
+        with_optional_params(
+            tool_definition(
+                "get_recent_activity",
+                "Summarize what changed over the last days as a markdown digest",
+                &[],
+            ),
+            &[
+                (
+                    "collection_id",
+                    "string",
+                    "Collection to summarize, omit for the whole workspace (optional)",
+                ),
+                ("days", "number", "Days to look back, default 1 (optional)"),
+            ],
+        ),
+    ]
+}
+
+/// Call event tool
+pub async fn call_event_tool(
+    name: &str,
+    arguments: Value,
+    client: &OutlineClient,
+) -> Result<Value> {
+    match name {
+        "list_events" => list_events(arguments, client).await,
+        "get_recent_activity" => get_recent_activity(arguments, client).await,
+        _ => unreachable!("Unknown event tool: {}", name),
+    }
+}
+
+async fn list_events(args: Value, client: &OutlineClient) -> Result<Value> {
+    let limit = get_optional_number_arg(&args, "limit");
+    let offset = get_optional_number_arg(&args, "offset");
+    let audit_log = get_optional_bool_arg(&args, "audit_log");
+
+    debug!("Listing events");
+
+    let mut request_body = json!({ "sort": "createdAt", "direction": "DESC" });
+    for (arg, field) in [
+        ("actor_id", "actorId"),
+        ("document_id", "documentId"),
+        ("collection_id", "collectionId"),
+        ("name", "name"),
+    ] {
+        if let Some(value) = get_optional_string_arg(&args, arg) {
+            request_body[field] = json!(value);
+        }
+    }
+    if let Some(audit) = audit_log {
+        request_body["auditLog"] = json!(audit);
+    }
+    if let Some(lim) = limit {
+        request_body["limit"] = json!(lim);
+    }
+    if let Some(off) = offset {
+        request_body["offset"] = json!(off);
+    }
+
+    let response = client.post("events.list", request_body).await?;
+
+    Ok(create_mcp_success_response(
+        "Events listed successfully",
+        Some(response),
+    ))
+}
+
+async fn get_recent_activity(args: Value, client: &OutlineClient) -> Result<Value> {
+    let collection_id = get_optional_string_arg(&args, "collection_id");
+    let days = get_optional_number_arg(&args, "days")
+        .and_then(|d| u64::try_from(d).ok())
+        .filter(|d| *d > 0)
+        .unwrap_or(1);
+    let since = iso_timestamp(unix_now().saturating_sub(days.saturating_mul(86_400)));
+
+    debug!(
+        "Collecting activity since {} for {:?}",
+        since, collection_id
+    );
+
+    let mut events = Vec::new();
+    let mut complete = false;
+    'pages: while events.len() < MAX_DIGEST_EVENTS {
+        let mut request_body = json!({
+            "sort": "createdAt",
+            "direction": "DESC",
+            "limit": EVENTS_PAGE_SIZE,
+            "offset": events.len()
+        });
+        if let Some(cid) = &collection_id {
+            request_body["collectionId"] = json!(cid);
+        }
+
+        let response = client.post("events.list", request_body).await?;
+        let page = response["data"].as_array().cloned().unwrap_or_default();
+        let page_len = page.len();
+
+        for event in page {
+            if event["createdAt"].as_str().unwrap_or_default() < since.as_str() {
+                complete = true;
+                break 'pages;
+            }
+            events.push(event);
+        }
+        if page_len < EVENTS_PAGE_SIZE {
+            complete = true;
+            break;
+        }
+    }
+    let truncated = !complete;
+
+    let mut titles = HashMap::new();
+    for event in &events {
+        if titles.len() >= MAX_TITLE_LOOKUPS {
+            break;
+        }
+        let Some(id) = event["documentId"].as_str() else {
+            continue;
+        };
+        if titles.contains_key(id) {
+            continue;
+        }
+        // Deleted documents can no longer be looked up, fall back to event data
+        let title = client
+            .post("documents.info", json!({ "id": id }))
+            .await
+            .map_or_else(
+                |_| event["data"]["title"].as_str().map(String::from),
+                |response| response["data"]["title"].as_str().map(String::from),
+            );
+        titles.insert(id.to_string(), title.unwrap_or_else(|| id.to_string()));
+    }
+
+    let digest = summarize_activity(&events, &titles, days, &since, truncated);
+
+    // Events are newest first, so the covered range runs from the last to the first
+    let mut response = create_mcp_success_response(&digest, None);
+    response["structuredContent"] = json!({
+        "since": since,
+        "events": events.len(),
+        "truncated": truncated,
+        "coveredFrom": events.last().map(|e| &e["createdAt"]),
+        "coveredUntil": events.first().map(|e| &e["createdAt"])
+    });
+    Ok(response)
+}
+
+/// Per-document tally of event labels and actors
+#[derive(Default)]
+struct DocumentActivity {
+    actions: BTreeMap<String, usize>,
+    actors: Vec<String>,
+    latest: String,
+}
+
+/// Render events (newest first) as a markdown digest grouped by document
+///
+/// `truncated` means the event cap was hit before `since`, so the counts only
+/// cover the period back to the oldest event read.
+fn summarize_activity(
+    events: &[Value],
+    titles: &HashMap<String, String>,
+    days: u64,
+    since: &str,
+    truncated: bool,
+) -> String {
+    let period = if days == 1 {
+        "the last day".to_string()
+    } else {
+        format!("the last {days} days")
+    };
+    let mut output = format!("# Activity over {period}\n\nSince {since}.\n");
+
+    if events.is_empty() {
+        output.push_str("\nNo changes.\n");
+        return output;
+    }
+
+    let mut order = Vec::new();
+    let mut documents: HashMap<&str, DocumentActivity> = HashMap::new();
+    let mut other: BTreeMap<String, usize> = BTreeMap::new();
+    let mut actors: Vec<&str> = Vec::new();
+
+    for event in events {
+        let name = event["name"].as_str().unwrap_or("unknown");
+        let actor = event["actor"]["name"].as_str().unwrap_or("Unknown");
+        if !actors.contains(&actor) {
+            actors.push(actor);
+        }
+
+        let Some(document_id) = event["documentId"].as_str() else {
+            *other.entry(name.to_string()).or_default() += 1;
+            continue;
+        };
+
+        let activity = documents.entry(document_id).or_insert_with(|| {
+            order.push(document_id);
+            DocumentActivity {
+                latest: event["createdAt"].as_str().unwrap_or_default().to_string(),
+                ..DocumentActivity::default()
+            }
+        });
+        *activity.actions.entry(action_label(name)).or_default() += 1;
+        if !activity.actors.iter().any(|a| a == actor) {
+            activity.actors.push(actor.to_string());
+        }
+    }
+
+    let _ = writeln!(
+        output,
+        "\n{} events by {} people across {} documents.",
+        events.len(),
+        actors.len(),
+        documents.len()
+    );
+    if truncated {
+        let oldest = events
+            .last()
+            .and_then(|event| event["createdAt"].as_str())
+            .unwrap_or(since);
+        let _ = writeln!(
+            output,
+            "\nTruncated: only the latest {} events, back to {oldest}, are counted.",
+            events.len()
+        );
+    }
+
+    if !order.is_empty() {
+        output.push_str("\n## Documents\n\n");
+        for id in order {
+            let activity = &documents[id];
+            let title = titles.get(id).map_or(id, String::as_str);
+            let actions = activity
+                .actions
+                .iter()
+                .map(|(label, count)| {
+                    if *count == 1 {
+                        label.clone()
+                    } else {
+                        format!("{label} ×{count}")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(
+                output,
+                "- **{title}** `{id}`: {actions} by {} (last {})",
+                activity.actors.join(", "),
+                activity.latest
+            );
+        }
+    }
+
+    if !other.is_empty() {
+        output.push_str("\n## Other\n\n");
+        for (name, count) in other {
+            let _ = writeln!(output, "- {name}: {count}");
+        }
+    }
+
+    output
+}
+
+/// Short label for a document event name, e.g. `documents.update` -> `updated`
+fn action_label(name: &str) -> String {
+    let action = name.rsplit('.').next().unwrap_or(name);
+    match action {
+        "create" => "created".to_string(),
+        "publish" => "published".to_string(),
+        "unpublish" => "unpublished".to_string(),
+        "update" => "updated".to_string(),
+        "archive" => "archived".to_string(),
+        "unarchive" => "unarchived".to_string(),
+        "delete" => "deleted".to_string(),
+        "permanent_delete" => "permanently deleted".to_string(),
+        "restore" => "restored".to_string(),
+        "move" => "moved".to_string(),
+        "title_change" => "renamed".to_string(),
+        other => other.replace('_', " "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::stand_in;
+
+    fn event(name: &str, document_id: Option<&str>, actor: &str, created: &str) -> Value {
+        json!({
+            "name": name,
+            "documentId": document_id,
+            "actor": { "name": actor },
+            "createdAt": created
+        })
+    }
+
+    #[test]
+    fn test_action_label() {
+        assert_eq!(action_label("documents.update"), "updated");
+        assert_eq!(action_label("documents.title_change"), "renamed");
+        assert_eq!(action_label("shares.custom_thing"), "custom thing");
+    }
+
+    #[tokio::test]
+    async fn test_digest_reports_event_cap() {
+        let client = stand_in::serve(vec![(
+            "events.list",
+            stand_in::fixed(json!({
+                "data": vec![json!({ "name": "users.signin", "createdAt": "2999-01-01T00:00:00.000Z" }); EVENTS_PAGE_SIZE]
+            })),
+        )])
+        .await;
+
+        let response = get_recent_activity(json!({}), &client).await.unwrap();
+        let content = &response["structuredContent"];
+        assert_eq!(content["truncated"], true);
+        assert_eq!(content["events"], MAX_DIGEST_EVENTS);
+        assert_eq!(content["coveredFrom"], "2999-01-01T00:00:00.000Z");
+        assert!(response["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("Truncated"));
+    }
+
+    #[test]
+    fn test_summarize_activity() {
+        let events = vec![
+            event(
+                "documents.update",
+                Some("d1"),
+                "Ann",
+                "2024-01-02T10:00:00.000Z",
+            ),
+            event(
+                "documents.update",
+                Some("d1"),
+                "Bob",
+                "2024-01-02T09:00:00.000Z",
+            ),
+            event(
+                "documents.create",
+                Some("d2"),
+                "Ann",
+                "2024-01-01T12:00:00.000Z",
+            ),
+            event(
+                "collections.update",
+                None,
+                "Ann",
+                "2024-01-01T11:00:00.000Z",
+            ),
+        ];
+        let titles = HashMap::from([("d1".to_string(), "Roadmap".to_string())]);
+
+        let digest = summarize_activity(&events, &titles, 2, "2024-01-01T00:00:00.000Z", false);
+        assert!(digest.starts_with("# Activity over the last 2 days"));
+        assert!(digest.contains("4 events by 2 people across 2 documents."));
+        assert!(digest.contains(
+            "- **Roadmap** `d1`: updated ×2 by Ann, Bob (last 2024-01-02T10:00:00.000Z)"
+        ));
+        assert!(digest.contains("- **d2** `d2`: created by Ann"));
+        assert!(digest.contains("- collections.update: 1"));
+        assert!(!digest.contains("Truncated"));
+
+        let partial = summarize_activity(&events, &titles, 2, "2023-12-01T00:00:00.000Z", true);
+        assert!(partial.contains(
+            "Truncated: only the latest 4 events, back to 2024-01-01T11:00:00.000Z, are counted."
+        ));
+
+        let empty = summarize_activity(&[], &titles, 1, "2024-01-01T00:00:00.000Z", false);
+        assert!(empty.contains("over the last day"));
+        assert!(empty.contains("No changes."));
+    }
+}
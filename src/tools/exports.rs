@@ -0,0 +1,401 @@
+//! Document and collection export tools
+
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tracing::debug;
+
+use super::common::{
+    create_mcp_resource_response, create_mcp_success_response, ensure_local_files_allowed,
+    get_optional_number_arg, get_optional_string_arg, get_string_arg, tool_definition,
+};
+use crate::error::{Error, Result};
+use crate::mcp::Transport;
+use crate::outline::Client as OutlineClient;
+
+/// Interval between `fileOperations.info` polls
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Default time to wait for a collection export to complete
+const DEFAULT_EXPORT_TIMEOUT_SECS: u64 = 300;
+
+/// Longest a caller may wait for a collection export
+const MAX_EXPORT_TIMEOUT_SECS: u64 = 1800;
+
+/// Largest archive returned inline; bigger ones must go to `output_path`
+const MAX_INLINE_EXPORT_BYTES: usize = 20 * 1024 * 1024;
+
+/// Get all export tool definitions
+pub fn get_export_tools() -> Vec<Value> {
+    vec![
+        tool_definition(
+            "export_document",
+            "Export document as markdown, HTML or PDF",
+            &[
+                ("id", "string", "Document ID"),
+                (
+                    "format",
+                    "string",
+                    "Export format: markdown (default), html or pdf (optional)",
+                ),
+                (
+                    "output_path",
+                    "string",
+                    "Write export to this local file instead of returning it, STDIO mode only (optional)",
+                ),
+            ],
+        ),
+        tool_definition(
+            "export_collection",
+            "Export collection (or whole workspace) and return the archive",
+            &[
+                (
+                    "collection_id",
+                    "string",
+                    "Collection ID (optional, omit to export all collections)",
+                ),
+                (
+                    "format",
+                    "string",
+                    "Export format: outline-markdown (default), json or html (optional)",
+                ),
+                (
+                    "timeout",
+                    "number",
+                    "Seconds to wait for the export to complete, default 300, at most 1800 (optional)",
+                ),
+                (
+                    "output_path",
+                    "string",
+                    "Write archive to this local file instead of returning it, STDIO mode only (optional)",
+                ),
+            ],
+        ),
+    ]
+}
+
+/// Call export tool
+pub async fn call_export_tool(
+    name: &str,
+    arguments: Value,
+    client: &OutlineClient,
+    transport: Transport,
+) -> Result<Value> {
+    match name {
+        "export_document" => export_document(arguments, client, transport).await,
+        "export_collection" => export_collection(arguments, client, transport).await,
+        _ => unreachable!("Unknown export tool: {}", name),
+    }
+}
+
+async fn export_document(
+    args: Value,
+    client: &OutlineClient,
+    transport: Transport,
+) -> Result<Value> {
+    let id = get_string_arg(&args, "id")?;
+    let format = get_optional_string_arg(&args, "format").unwrap_or_else(|| "markdown".into());
+    let output_path = get_optional_string_arg(&args, "output_path");
+    if output_path.is_some() {
+        ensure_local_files_allowed(transport, "export_document", "output_path")?;
+    }
+
+    let accept = match format.as_str() {
+        "markdown" | "md" => "text/markdown",
+        "html" => "text/html",
+        "pdf" => "application/pdf",
+        other => {
+            return Err(Error::Tool {
+                tool_name: "export_document".to_string(),
+                message: format!("Unsupported format '{other}', expected markdown, html or pdf"),
+                source: None,
+            })
+        }
+    };
+
+    debug!("Exporting document {} as {}", id, format);
+
+    let (content_type, bytes) = client
+        .post_bytes("documents.export", json!({ "id": id }), accept)
+        .await?;
+
+    // Older Outline versions ignore Accept and wrap markdown in a JSON envelope
+    let bytes = if content_type.starts_with("application/json") {
+        let envelope: Value = serde_json::from_slice(&bytes)?;
+        envelope["data"]
+            .as_str()
+            .unwrap_or_default()
+            .as_bytes()
+            .to_vec()
+    } else {
+        bytes.to_vec()
+    };
+
+    if let Some(path) = output_path {
+        write_local_file(&path, &bytes)?;
+        return Ok(create_mcp_success_response(
+            &format!("Document exported to {path} ({} bytes)", bytes.len()),
+            None,
+        ));
+    }
+
+    if accept == "application/pdf" {
+        return Ok(create_mcp_resource_response(
+            "Document exported successfully",
+            &format!("outline://documents/{id}/export.pdf"),
+            accept,
+            &bytes,
+            None,
+        ));
+    }
+
+    let text = String::from_utf8(bytes)?;
+    Ok(create_mcp_success_response(&text, None))
+}
+
+async fn export_collection(
+    args: Value,
+    client: &OutlineClient,
+    transport: Transport,
+) -> Result<Value> {
+    let collection_id = get_optional_string_arg(&args, "collection_id");
+    let format =
+        get_optional_string_arg(&args, "format").unwrap_or_else(|| "outline-markdown".into());
+    let timeout = get_optional_number_arg(&args, "timeout")
+        .and_then(|t| u64::try_from(t).ok())
+        .unwrap_or(DEFAULT_EXPORT_TIMEOUT_SECS)
+        .min(MAX_EXPORT_TIMEOUT_SECS);
+    let output_path = get_optional_string_arg(&args, "output_path");
+    if output_path.is_some() {
+        ensure_local_files_allowed(transport, "export_collection", "output_path")?;
+    }
+
+    debug!("Exporting collection {:?} as {}", collection_id, format);
+
+    let response = if let Some(cid) = &collection_id {
+        client
+            .post("collections.export", json!({ "id": cid, "format": format }))
+            .await?
+    } else {
+        client
+            .post("collections.export_all", json!({ "format": format }))
+            .await?
+    };
+
+    let operation_id = response["data"]["fileOperation"]["id"]
+        .as_str()
+        .ok_or_else(|| Error::Tool {
+            tool_name: "export_collection".to_string(),
+            message: "Export response did not contain a file operation".to_string(),
+            source: None,
+        })?
+        .to_string();
+
+    let operation = wait_for_file_operation(client, &operation_id, timeout).await?;
+    let name = operation["name"].as_str().unwrap_or("export.zip");
+
+    if let Some(path) = output_path {
+        let size = download_to_file(client, &operation_id, &path).await?;
+        return Ok(create_mcp_success_response(
+            &format!("Export {name} written to {path} ({size} bytes)"),
+            None,
+        ));
+    }
+
+    // Check the reported size first so that oversized archives are not downloaded
+    if operation["size"]
+        .as_u64()
+        .is_some_and(|size| size > MAX_INLINE_EXPORT_BYTES as u64)
+    {
+        return Err(too_large_to_inline(name));
+    }
+
+    let (content_type, bytes) =
+        download_inline(client, &operation_id, name, MAX_INLINE_EXPORT_BYTES).await?;
+
+    Ok(create_mcp_resource_response(
+        &format!("Export {name} completed ({} bytes)", bytes.len()),
+        &format!("outline://fileOperations/{operation_id}/{name}"),
+        &content_type,
+        &bytes,
+        Some(json!({ "data": operation })),
+    ))
+}
+
+/// Stream a finished export into the local file at `path`, returning its size
+async fn download_to_file(client: &OutlineClient, operation_id: &str, path: &str) -> Result<usize> {
+    let mut file =
+        std::fs::File::create(path).map_err(|e| Error::io(format!("Failed to write {path}"), e))?;
+    let mut size = 0;
+    let download = client
+        .post_streamed(
+            "fileOperations.redirect",
+            json!({ "id": operation_id }),
+            "*/*",
+            |chunk| {
+                size += chunk.len();
+                file.write_all(chunk)
+                    .map_err(|e| Error::io(format!("Failed to write {path}"), e))
+            },
+        )
+        .await;
+
+    // Do not leave a partial archive behind
+    if let Err(e) = download {
+        let _ = std::fs::remove_file(path);
+        return Err(e);
+    }
+    Ok(size)
+}
+
+/// Download a finished export into memory, giving up past `max_bytes`
+async fn download_inline(
+    client: &OutlineClient,
+    operation_id: &str,
+    name: &str,
+    max_bytes: usize,
+) -> Result<(String, Vec<u8>)> {
+    let mut bytes = Vec::new();
+    let content_type = client
+        .post_streamed(
+            "fileOperations.redirect",
+            json!({ "id": operation_id }),
+            "*/*",
+            |chunk| {
+                if bytes.len() + chunk.len() > max_bytes {
+                    return Err(too_large_to_inline(name));
+                }
+                bytes.extend_from_slice(chunk);
+                Ok(())
+            },
+        )
+        .await?;
+    Ok((content_type, bytes))
+}
+
+/// Error for an archive above [`MAX_INLINE_EXPORT_BYTES`]
+fn too_large_to_inline(name: &str) -> Error {
+    Error::Tool {
+        tool_name: "export_collection".to_string(),
+        message: format!(
+            "Export {name} is larger than the {MAX_INLINE_EXPORT_BYTES} bytes returned inline; \
+             pass output_path to write it to a local file or download it from Outline"
+        ),
+        source: None,
+    }
+}
+
+/// Poll `fileOperations.info` until the operation completes, fails or times out
+async fn wait_for_file_operation(
+    client: &OutlineClient,
+    operation_id: &str,
+    timeout_secs: u64,
+) -> Result<Value> {
+    let started = Instant::now();
+
+    loop {
+        let response = client
+            .post("fileOperations.info", json!({ "id": operation_id }))
+            .await?;
+        let operation = response["data"].clone();
+        let state = operation["state"].as_str().unwrap_or_default();
+
+        debug!("File operation {} state: {}", operation_id, state);
+
+        match state {
+            "complete" => return Ok(operation),
+            "error" | "expired" => {
+                return Err(Error::Tool {
+                    tool_name: "export_collection".to_string(),
+                    message: format!(
+                        "Export {operation_id} failed ({state}): {}",
+                        operation["error"].as_str().unwrap_or("no details")
+                    ),
+                    source: None,
+                })
+            }
+            _ => {}
+        }
+
+        if started.elapsed() >= Duration::from_secs(timeout_secs) {
+            return Err(Error::Tool {
+                tool_name: "export_collection".to_string(),
+                message: format!(
+                    "Export {operation_id} did not complete within {timeout_secs}s (last state: {state})"
+                ),
+                source: None,
+            });
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Write exported data to a local file
+fn write_local_file(path: &str, data: &[u8]) -> Result<()> {
+    std::fs::write(path, data).map_err(|e| Error::io(format!("Failed to write {path}"), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::stand_in;
+
+    #[tokio::test]
+    async fn test_oversized_export_is_not_inlined() {
+        let client = stand_in::serve(vec![
+            (
+                "collections.export",
+                stand_in::fixed(json!({ "data": { "fileOperation": { "id": "op-1" } } })),
+            ),
+            (
+                "fileOperations.info",
+                stand_in::fixed(json!({
+                    "data": { "id": "op-1", "state": "complete", "name": "big.zip", "size": 50_000_000 }
+                })),
+            ),
+        ])
+        .await;
+
+        let args = json!({ "collection_id": "col-1", "timeout": 1_000_000 });
+        let err = export_collection(args, &client, Transport::Http)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("output_path"));
+    }
+
+    fn archive_stand_in() -> Vec<(&'static str, stand_in::Handler)> {
+        vec![(
+            "fileOperations.redirect",
+            std::sync::Arc::new(|_: &Value| (200, "application/zip", b"0123456789".to_vec())),
+        )]
+    }
+
+    #[tokio::test]
+    async fn test_inline_download_stops_at_cap() {
+        let client = stand_in::serve(archive_stand_in()).await;
+
+        let err = download_inline(&client, "op-1", "big.zip", 4)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("larger than"));
+
+        let (content_type, bytes) = download_inline(&client, "op-1", "big.zip", 10)
+            .await
+            .unwrap();
+        assert_eq!(content_type, "application/zip");
+        assert_eq!(bytes, b"0123456789");
+    }
+
+    #[tokio::test]
+    async fn test_download_streams_to_file() {
+        let client = stand_in::serve(archive_stand_in()).await;
+        let path = std::env::temp_dir().join(format!("export-{}.zip", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+
+        assert_eq!(download_to_file(&client, "op-1", path).await.unwrap(), 10);
+        assert_eq!(std::fs::read(path).unwrap(), b"0123456789");
+        std::fs::remove_file(path).unwrap();
+    }
+}
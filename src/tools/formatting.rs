@@ -0,0 +1,574 @@
+//! Response formatting
+//!
+//! Tool results keep the full Outline response in `structuredContent`. The
+//! `text` content is rendered for the model instead of dumping that JSON:
+//! markdown tables for lists and metadata plus body for single items by
+//! default, one line per item with `summary`, or the raw JSON with `json`.
+
+use std::fmt::Write;
+
+use serde_json::{json, Value};
+
+use super::comments::comment_text;
+use super::common::get_optional_string_arg;
+use crate::error::{Error, Result};
+
+/// `format` parameter added to every tool with a structured result
+pub const FORMAT_PARAM: (&str, &str, &str) = (
+    "format",
+    "string",
+    "Response text format: summary, markdown or json, default markdown (optional)",
+);
+
+/// Tools returning plain text or files, where `format` has no effect
+pub const PLAIN_TEXT_TOOLS: &[&str] = &[
+    "export_document",
+    "export_collection",
+    "get_collection_tree",
+    "get_document_discussion",
+    "get_recent_activity",
+];
+
+/// Longest table cell before it is cut off
+const MAX_CELL_CHARS: usize = 160;
+
+/// How the text content of a tool result is rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    /// One line per item, metadata only
+    Summary,
+    /// Tables for lists, metadata and body for single items
+    Markdown,
+    /// Full Outline response as JSON
+    Json,
+}
+
+impl ResponseFormat {
+    pub fn from_args(args: &Value, tool_name: &str) -> Result<Self> {
+        match get_optional_string_arg(args, FORMAT_PARAM.0).as_deref() {
+            None | Some("markdown") => Ok(Self::Markdown),
+            Some("summary") => Ok(Self::Summary),
+            Some("json") => Ok(Self::Json),
+            Some(other) => Err(Error::Tool {
+                tool_name: tool_name.to_string(),
+                message: format!("Invalid format '{other}', expected summary, markdown or json"),
+                source: None,
+            }),
+        }
+    }
+}
+
+/// Where a column takes its value from
+#[derive(Clone, Copy)]
+enum Cell<'a> {
+    /// JSON pointer into the item
+    Field(&'a str),
+    /// Text derived from the whole item
+    Text(fn(&Value) -> String),
+}
+
+/// Columns and body shown for the items a tool returns
+struct Layout {
+    /// Header and source of each column, `ID` identifies the item
+    columns: &'static [(&'static str, Cell<'static>)],
+    /// Whether single items get their first column as a heading
+    heading: bool,
+    /// Markdown body shown for single items
+    body: Option<Cell<'static>>,
+}
+
+const DOCUMENTS: Layout = Layout {
+    columns: &[
+        ("Title", Cell::Field("/title")),
+        ("ID", Cell::Field("/id")),
+        ("Updated", Cell::Field("/updatedAt")),
+        ("URL", Cell::Field("/url")),
+    ],
+    heading: true,
+    body: Some(Cell::Field("/text")),
+};
+
+const SEARCH_RESULTS: Layout = Layout {
+    columns: &[
+        ("Title", Cell::Field("/document/title")),
+        ("ID", Cell::Field("/document/id")),
+        ("Updated", Cell::Field("/document/updatedAt")),
+        ("URL", Cell::Field("/document/url")),
+        ("Context", Cell::Field("/context")),
+    ],
+    heading: true,
+    body: None,
+};
+
+const COLLECTIONS: Layout = Layout {
+    columns: &[
+        ("Name", Cell::Field("/name")),
+        ("ID", Cell::Field("/id")),
+        ("Updated", Cell::Field("/updatedAt")),
+        ("URL", Cell::Field("/url")),
+    ],
+    heading: true,
+    body: Some(Cell::Field("/description")),
+};
+
+const USERS: Layout = Layout {
+    columns: &[
+        ("Name", Cell::Field("/name")),
+        ("ID", Cell::Field("/id")),
+        ("Email", Cell::Field("/email")),
+        ("Role", Cell::Field("/role")),
+        ("Last active", Cell::Field("/lastActiveAt")),
+    ],
+    heading: true,
+    body: None,
+};
+
+const COMMENTS: Layout = Layout {
+    columns: &[
+        ("Author", Cell::Field("/createdBy/name")),
+        ("ID", Cell::Field("/id")),
+        ("Created", Cell::Field("/createdAt")),
+        ("Resolved", Cell::Field("/resolvedAt")),
+        ("Text", Cell::Text(comment_text)),
+    ],
+    heading: false,
+    body: None,
+};
+
+const EVENTS: Layout = Layout {
+    columns: &[
+        ("Event", Cell::Field("/name")),
+        ("ID", Cell::Field("/id")),
+        ("Actor", Cell::Field("/actor/name")),
+        ("Document", Cell::Field("/documentId")),
+        ("Created", Cell::Field("/createdAt")),
+    ],
+    heading: false,
+    body: None,
+};
+
+/// Fallback for everything else, columns missing from all items are dropped
+const GENERIC: Layout = Layout {
+    columns: &[
+        ("Title", Cell::Field("/title")),
+        ("Name", Cell::Field("/name")),
+        ("ID", Cell::Field("/id")),
+        ("Status", Cell::Field("/status")),
+        ("Document", Cell::Field("/documentId")),
+        ("Collection", Cell::Field("/collectionId")),
+        ("Created", Cell::Field("/createdAt")),
+        ("Updated", Cell::Field("/updatedAt")),
+        ("URL", Cell::Field("/url")),
+        ("Error", Cell::Field("/error")),
+    ],
+    heading: false,
+    body: None,
+};
+
+fn layout_for(tool_name: &str) -> &'static Layout {
+    match tool_name {
+        "create_document"
+        | "get_document"
+        | "get_document_chunk"
+        | "update_document"
+        | "unpublish_document"
+        | "list_documents"
+        | "archive_document"
+        | "restore_document"
+        | "unarchive_document"
+        | "list_drafts"
+        | "create_template_from_document"
+        | "list_child_documents"
+        | "list_templates"
+        | "get_template"
+        | "create_document_from_template"
+        | "list_backlinks"
+        | "find_stale_documents"
+        | "find_orphan_documents" => &DOCUMENTS,
+        "search_documents" => &SEARCH_RESULTS,
+        "create_collection" | "get_collection" | "update_collection" | "list_collections" => {
+            &COLLECTIONS
+        }
+        "list_users" | "get_user" => &USERS,
+        "create_comment"
+        | "get_comment"
+        | "update_comment"
+        | "resolve_comment"
+        | "unresolve_comment"
+        | "list_document_comments" => &COMMENTS,
+        "list_events" => &EVENTS,
+        _ => &GENERIC,
+    }
+}
+
+/// Replace the text of a success response with the requested rendering
+///
+/// With `fields`, lists get one column per field instead of the tool's layout.
+pub fn format_response(
+    tool_name: &str,
+    format: ResponseFormat,
+    fields: Option<&[String]>,
+    mut response: Value,
+) -> Value {
+    let Some(content) = response.get("structuredContent") else {
+        return response;
+    };
+
+    let text = if format == ResponseFormat::Json {
+        // Re-serialize, the content may have been projected or truncated
+        serde_json::to_string(content).unwrap_or_default()
+    } else {
+        render(layout_for(tool_name), format, content, fields)
+    };
+    response["content"] = json!([{ "type": "text", "text": text }]);
+    response
+}
+
+/// Render structured content as markdown
+fn render(
+    layout: &Layout,
+    format: ResponseFormat,
+    content: &Value,
+    fields: Option<&[String]>,
+) -> String {
+    let mut output = String::new();
+
+    // Report fields around `data`, e.g. bulk counters or unresolved mentions
+    if let Some(fields) = content.as_object() {
+        for (key, value) in fields {
+            if !matches!(key.as_str(), "data" | "pagination" | "policies") {
+                write_field(&mut output, key, value);
+            }
+        }
+    }
+
+    match &content["data"] {
+        Value::Array(items) => {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            match fields {
+                Some(fields) => {
+                    let pointers: Vec<String> = fields
+                        .iter()
+                        .map(|field| format!("/{}", field.replace('.', "/")))
+                        .collect();
+                    let columns: Vec<(&str, Cell)> = fields
+                        .iter()
+                        .zip(&pointers)
+                        .map(|(field, pointer)| (field.as_str(), Cell::Field(pointer)))
+                        .collect();
+                    render_list(&mut output, &columns, format, items);
+                }
+                None => render_list(&mut output, layout.columns, format, items),
+            }
+            if let Some(offset) = next_offset(&content["pagination"], items.len()) {
+                let _ = writeln!(output, "\nMore results available with offset {offset}.");
+            }
+        }
+        item @ Value::Object(_) => {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            if fields.is_some() {
+                write_fields(&mut output, item);
+            } else {
+                render_item(&mut output, layout, format, item);
+            }
+        }
+        Value::Null => {}
+        other => write_field(&mut output, "data", other),
+    }
+
+    if output.is_empty() {
+        "Done.".to_string()
+    } else {
+        output.trim_end().to_string()
+    }
+}
+
+fn render_list(
+    output: &mut String,
+    columns: &[(&str, Cell)],
+    format: ResponseFormat,
+    items: &[Value],
+) {
+    if items.is_empty() {
+        output.push_str("No results.\n");
+        return;
+    }
+
+    let columns: Vec<&(&str, Cell)> = columns
+        .iter()
+        .filter(|(_, cell)| items.iter().any(|item| !cell_text(item, *cell).is_empty()))
+        .collect();
+    if columns.is_empty() {
+        let _ = writeln!(output, "{} items.", items.len());
+        return;
+    }
+
+    if format == ResponseFormat::Summary {
+        let id_column = columns.iter().find(|(header, _)| *header == "ID");
+        for item in items {
+            let label = cell_text(item, columns[0].1);
+            match id_column.map(|(_, cell)| cell_text(item, *cell)) {
+                Some(id) if !id.is_empty() && id != label => {
+                    let _ = writeln!(output, "- {} (`{id}`)", truncate(&label));
+                }
+                _ => {
+                    let _ = writeln!(output, "- {}", truncate(&label));
+                }
+            }
+        }
+        return;
+    }
+
+    let headers: Vec<&str> = columns.iter().map(|(header, _)| *header).collect();
+    let _ = writeln!(output, "| {} |", headers.join(" | "));
+    let _ = writeln!(output, "|{}", "---|".repeat(headers.len()));
+    for item in items {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|(_, cell)| escape_cell(&cell_text(item, *cell)))
+            .collect();
+        let _ = writeln!(output, "| {} |", cells.join(" | "));
+    }
+}
+
+fn render_item(output: &mut String, layout: &Layout, format: ResponseFormat, item: &Value) {
+    if std::ptr::eq(layout, &GENERIC) {
+        write_fields(output, item);
+        return;
+    }
+
+    let mut columns = layout.columns.iter();
+    if layout.heading {
+        if let Some((_, cell)) = columns.next() {
+            let _ = writeln!(output, "# {}\n", cell_text(item, *cell));
+        }
+    }
+    for (header, cell) in columns {
+        let value = cell_text(item, *cell);
+        if !value.is_empty() {
+            let _ = writeln!(output, "- **{header}**: {value}");
+        }
+    }
+
+    if format == ResponseFormat::Markdown {
+        if let Some(body) = layout.body.map(|cell| cell_text(item, cell)) {
+            if !body.trim().is_empty() {
+                let _ = writeln!(output, "\n{}", body.trim_end());
+            }
+        }
+    }
+}
+
+fn write_fields(output: &mut String, item: &Value) {
+    if let Some(fields) = item.as_object() {
+        for (key, value) in fields {
+            write_field(output, key, value);
+        }
+    }
+}
+
+/// Write a report field as a bullet, lists of objects as a nested table
+fn write_field(output: &mut String, key: &str, value: &Value) {
+    match value {
+        Value::Null => {}
+        Value::String(text) if text.contains('\n') => {
+            let _ = writeln!(output, "**{key}**:\n\n{}\n", text.trim_end());
+        }
+        Value::Array(items) if items.iter().any(Value::is_object) => {
+            let _ = writeln!(output, "**{key}**:\n");
+            render_list(output, GENERIC.columns, ResponseFormat::Markdown, items);
+            output.push('\n');
+        }
+        Value::Array(items) => {
+            let values: Vec<String> = items.iter().map(scalar_text).collect();
+            let _ = writeln!(output, "- **{key}**: {}", values.join(", "));
+        }
+        Value::Object(fields) => {
+            for (sub_key, sub_value) in fields {
+                if !sub_value.is_object() && !sub_value.is_array() && !sub_value.is_null() {
+                    let _ = writeln!(output, "- **{key}.{sub_key}**: {}", scalar_text(sub_value));
+                }
+            }
+        }
+        scalar => {
+            let _ = writeln!(output, "- **{key}**: {}", scalar_text(scalar));
+        }
+    }
+}
+
+fn cell_text(item: &Value, cell: Cell) -> String {
+    match cell {
+        Cell::Field(pointer) => item.pointer(pointer).map_or_else(String::new, scalar_text),
+        Cell::Text(text) => text(item),
+    }
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Single-line table cell with search highlights turned into bold
+fn escape_cell(text: &str) -> String {
+    let text = text
+        .replace("<b>", "**")
+        .replace("</b>", "**")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|");
+    truncate(&text)
+}
+
+fn truncate(text: &str) -> String {
+    match text.char_indices().nth(MAX_CELL_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// Offset of the next page when the current one is full
+fn next_offset(pagination: &Value, returned: usize) -> Option<u64> {
+    let limit = pagination["limit"].as_u64()?;
+    let offset = pagination["offset"].as_u64().unwrap_or(0);
+    (returned as u64 >= limit && limit > 0).then_some(offset + limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::common::create_mcp_success_response;
+
+    fn document(id: &str, title: &str) -> Value {
+        json!({
+            "id": id,
+            "title": title,
+            "text": "# Body\n\nLong content",
+            "updatedAt": "2024-01-02T00:00:00.000Z",
+            "url": format!("/doc/{id}")
+        })
+    }
+
+    #[test]
+    fn test_response_format_from_args() {
+        let parse = |args: Value| ResponseFormat::from_args(&args, "tool");
+        assert_eq!(parse(json!({})).unwrap(), ResponseFormat::Markdown);
+        assert_eq!(
+            parse(json!({ "format": "summary" })).unwrap(),
+            ResponseFormat::Summary
+        );
+        assert_eq!(
+            parse(json!({ "format": "json" })).unwrap(),
+            ResponseFormat::Json
+        );
+        assert!(parse(json!({ "format": "xml" })).is_err());
+    }
+
+    #[test]
+    fn test_format_list_as_table() {
+        let response = create_mcp_success_response(
+            "Documents listed successfully",
+            Some(json!({
+                "data": [document("d1", "Roadmap | 2024"), document("d2", "Notes")],
+                "pagination": { "limit": 2, "offset": 0 }
+            })),
+        );
+        let formatted = format_response("list_documents", ResponseFormat::Markdown, None, response);
+        let text = formatted["content"][0]["text"].as_str().unwrap();
+
+        assert!(text.starts_with("| Title | ID | Updated | URL |"));
+        assert!(text.contains("| Roadmap \\| 2024 | d1 | 2024-01-02T00:00:00.000Z | /doc/d1 |"));
+        assert!(!text.contains("Long content"));
+        assert!(text.ends_with("More results available with offset 2."));
+        assert_eq!(
+            formatted["structuredContent"]["data"][0]["text"],
+            "# Body\n\nLong content"
+        );
+    }
+
+    #[test]
+    fn test_format_single_document() {
+        let content = json!({ "data": document("d1", "Roadmap") });
+
+        let markdown = render(&DOCUMENTS, ResponseFormat::Markdown, &content, None);
+        assert!(markdown.starts_with("# Roadmap\n\n- **ID**: d1\n"));
+        assert!(markdown.ends_with("# Body\n\nLong content"));
+
+        let summary = render(&DOCUMENTS, ResponseFormat::Summary, &content, None);
+        assert!(summary.contains("- **URL**: /doc/d1"));
+        assert!(!summary.contains("Long content"));
+    }
+
+    #[test]
+    fn test_format_summary_and_report_fields() {
+        let content = json!({
+            "total": 2,
+            "dryRun": false,
+            "data": [
+                { "id": "d1", "status": "moved" },
+                { "id": "d2", "status": "failed", "error": "Not found" }
+            ]
+        });
+
+        let summary = render(&GENERIC, ResponseFormat::Summary, &content, None);
+        assert!(summary.contains("- **total**: 2\n"));
+        assert!(summary.contains("- **dryRun**: false\n"));
+        assert!(summary.ends_with("- d1\n- d2"));
+
+        let markdown = render(&GENERIC, ResponseFormat::Markdown, &content, None);
+        assert!(markdown.contains("| ID | Status | Error |"));
+        assert!(markdown.contains("| d2 | failed | Not found |"));
+    }
+
+    #[test]
+    fn test_format_json_and_plain_text_untouched() {
+        let response = create_mcp_success_response("Done", Some(json!({ "data": [] })));
+        let json_text = response["content"][0]["text"].clone();
+        assert_eq!(
+            format_response("list_documents", ResponseFormat::Json, None, response)["content"][0]
+                ["text"],
+            json_text
+        );
+
+        let plain = create_mcp_success_response("# Outline", None);
+        assert_eq!(
+            format_response("get_collection_tree", ResponseFormat::Markdown, None, plain)
+                ["content"][0]["text"],
+            "# Outline"
+        );
+    }
+
+    #[test]
+    fn test_format_projected_fields() {
+        let content = json!({
+            "data": [{ "id": "d1", "collectionId": "c1", "createdBy": { "name": "Ann" } }]
+        });
+        let fields = ["id", "collectionId", "createdBy.name"].map(String::from);
+        let markdown = render(
+            &DOCUMENTS,
+            ResponseFormat::Markdown,
+            &content,
+            Some(&fields),
+        );
+        assert!(markdown.starts_with("| id | collectionId | createdBy.name |"));
+        assert!(markdown.contains("| d1 | c1 | Ann |"));
+
+        let single = json!({ "data": { "id": "d1", "title": "Roadmap" } });
+        let markdown = render(&DOCUMENTS, ResponseFormat::Markdown, &single, Some(&fields));
+        assert_eq!(markdown, "- **id**: d1\n- **title**: Roadmap");
+    }
+
+    #[test]
+    fn test_truncate() {
+        let long = "x".repeat(MAX_CELL_CHARS + 10);
+        assert_eq!(truncate(&long).chars().count(), MAX_CELL_CHARS + 1);
+        assert_eq!(truncate("short"), "short");
+    }
+}
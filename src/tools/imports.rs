@@ -0,0 +1,278 @@
+//! Document import tools
+
+use std::path::Path;
+
+use serde_json::{json, Value};
+use tracing::{debug, info};
+
+use super::common::{
+    create_mcp_success_response, ensure_local_files_allowed, get_optional_bool_arg,
+    get_optional_string_arg, get_optional_string_array_arg, tool_definition, with_optional_params,
+};
+use crate::error::{Error, Result};
+use crate::mcp::Transport;
+use crate::outline::Client as OutlineClient;
+
+/// Get all import tool definitions
+pub fn get_import_tools() -> Vec<Value> {
+    vec![with_optional_params(
+        tool_definition(
+            "import_document",
+            "Import markdown, HTML, DOCX or text as a new document",
+            &[],
+        ),
+        &[
+            (
+                "content",
+                "string",
+                "Inline file content (optional, use with file_name)",
+            ),
+            (
+                "content_base64",
+                "string",
+                "Inline binary content, e.g. DOCX, base64-encoded (optional, use with file_name)",
+            ),
+            (
+                "file_name",
+                "string",
+                "File name for inline content, extension selects the format, default import.md (optional)",
+            ),
+            (
+                "paths",
+                "array",
+                "Local file paths imported one at a time, STDIO mode only (optional)",
+            ),
+            ("collection_id", "string", "Target collection ID (optional)"),
+            (
+                "parent_document_id",
+                "string",
+                "Parent document ID to nest under (optional)",
+            ),
+            (
+                "publish",
+                "boolean",
+                "Publish imported documents immediately (optional)",
+            ),
+        ],
+    )]
+}
+
+/// Call import tool
+pub async fn call_import_tool(
+    name: &str,
+    arguments: Value,
+    client: &OutlineClient,
+    transport: Transport,
+) -> Result<Value> {
+    match name {
+        "import_document" => import_document(arguments, client, transport).await,
+        _ => unreachable!("Unknown import tool: {}", name),
+    }
+}
+
+async fn import_document(
+    args: Value,
+    client: &OutlineClient,
+    transport: Transport,
+) -> Result<Value> {
+    let content = get_optional_string_arg(&args, "content");
+    let content_base64 = get_optional_string_arg(&args, "content_base64");
+    let file_name = get_optional_string_arg(&args, "file_name");
+    let paths = get_optional_string_array_arg(&args, "paths").unwrap_or_default();
+    let collection_id = get_optional_string_arg(&args, "collection_id");
+    let parent_document_id = get_optional_string_arg(&args, "parent_document_id");
+    let publish = get_optional_bool_arg(&args, "publish");
+
+    let sources = [
+        content.is_some(),
+        content_base64.is_some(),
+        !paths.is_empty(),
+    ];
+    match sources.iter().filter(|given| **given).count() {
+        0 => {
+            return Err(import_error(
+                "One of 'content', 'content_base64' or 'paths' is required",
+            ))
+        }
+        1 => {}
+        _ => {
+            return Err(import_error(
+                "Only one of 'content', 'content_base64' or 'paths' may be given",
+            ))
+        }
+    }
+
+    if !paths.is_empty() {
+        ensure_local_files_allowed(transport, "import_document", "paths")?;
+    }
+
+    if collection_id.is_none() && parent_document_id.is_none() {
+        return Err(import_error(
+            "Either 'collection_id' or 'parent_document_id' is required",
+        ));
+    }
+
+    let mut fields = json!({});
+    if let Some(cid) = collection_id {
+        fields["collectionId"] = json!(cid);
+    }
+    if let Some(pid) = parent_document_id {
+        fields["parentDocumentId"] = json!(pid);
+    }
+    if let Some(p) = publish {
+        fields["publish"] = json!(p);
+    }
+
+    // Inline content is a single upload, reported like any other file
+    let inline = match (content, content_base64) {
+        (Some(text), _) => Some(text.into_bytes()),
+        (None, Some(encoded)) => {
+            use base64::Engine;
+            Some(
+                base64::engine::general_purpose::STANDARD
+                    .decode(encoded.trim())
+                    .map_err(|e| Error::Tool {
+                        tool_name: "import_document".to_string(),
+                        message: "Invalid base64 in 'content_base64'".to_string(),
+                        source: Some(Box::new(e)),
+                    })?,
+            )
+        }
+        (None, None) => None,
+    };
+
+    let mut results = Vec::new();
+
+    if let Some(data) = inline {
+        let name = file_name.unwrap_or_else(|| "import.md".to_string());
+        results.push(import_file(client, &fields, &name, data).await);
+    }
+
+    let total = paths.len();
+    for (index, path) in paths.iter().enumerate() {
+        info!("Importing file {}/{}: {}", index + 1, total, path);
+
+        let mut result = match std::fs::read(path) {
+            Ok(data) => {
+                let name = Path::new(path)
+                    .file_name()
+                    .map_or_else(|| path.clone(), |n| n.to_string_lossy().into_owned());
+                import_file(client, &fields, &name, data).await
+            }
+            Err(e) => json!({
+                "status": "failed",
+                "error": format!("Failed to read file: {e}")
+            }),
+        };
+
+        // Report the path as given rather than the bare file name
+        result["file"] = json!(path);
+        results.push(result);
+    }
+
+    let imported = results.iter().filter(|r| r["status"] == "imported").count();
+    let failed = results.len() - imported;
+
+    debug!("Import finished: {} imported, {} failed", imported, failed);
+
+    Ok(create_mcp_success_response(
+        "Import finished",
+        Some(json!({
+            "imported": imported,
+            "failed": failed,
+            "data": results
+        })),
+    ))
+}
+
+/// Upload one file to `documents.import` and describe the outcome
+async fn import_file(client: &OutlineClient, fields: &Value, name: &str, data: Vec<u8>) -> Value {
+    let Some(content_type) = import_content_type(name) else {
+        return json!({
+            "file": name,
+            "status": "failed",
+            "error": "Unsupported file type, expected .md, .markdown, .html, .htm, .docx, .txt or .csv"
+        });
+    };
+
+    match client
+        .post_multipart("documents.import", fields.clone(), name, content_type, data)
+        .await
+    {
+        Ok(response) => json!({
+            "file": name,
+            "status": "imported",
+            "id": response["data"]["id"],
+            "title": response["data"]["title"],
+            "url": response["data"]["url"]
+        }),
+        Err(e) => json!({
+            "file": name,
+            "status": "failed",
+            "error": e.to_string()
+        }),
+    }
+}
+
+/// Map a file name to the content type expected by `documents.import`
+fn import_content_type(file_name: &str) -> Option<&'static str> {
+    let extension = Path::new(file_name)
+        .extension()?
+        .to_str()?
+        .to_ascii_lowercase();
+
+    match extension.as_str() {
+        "md" | "markdown" => Some("text/markdown"),
+        "html" | "htm" => Some("text/html"),
+        "docx" => Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document"),
+        "txt" => Some("text/plain"),
+        "csv" => Some("text/csv"),
+        _ => None,
+    }
+}
+
+fn import_error(message: &str) -> Error {
+    Error::Tool {
+        tool_name: "import_document".to_string(),
+        message: message.to_string(),
+        source: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::stand_in;
+
+    #[test]
+    fn test_import_requires_no_source_in_schema() {
+        let tool = &get_import_tools()[0];
+        assert_eq!(tool["inputSchema"]["required"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_several_sources() {
+        let client = stand_in::serve(Vec::new()).await;
+        let args = json!({
+            "content": "# Notes",
+            "content_base64": "IyBOb3Rlcw==",
+            "collection_id": "col-1"
+        });
+
+        let err = import_document(args, &client, Transport::Stdio)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Only one of"));
+    }
+
+    #[test]
+    fn test_import_content_type() {
+        assert_eq!(import_content_type("notes.md"), Some("text/markdown"));
+        assert_eq!(import_content_type("Page.HTML"), Some("text/html"));
+        assert!(import_content_type("spec.docx")
+            .unwrap()
+            .contains("wordprocessingml"));
+        assert_eq!(import_content_type("image.png"), None);
+        assert_eq!(import_content_type("README"), None);
+    }
+}
@@ -0,0 +1,293 @@
+//! Link graph tools
+//!
+//! Backlinks, outgoing internal links and broken link detection.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use tracing::debug;
+
+use super::common::{
+    create_mcp_success_response, get_optional_number_arg, get_string_arg, tool_definition,
+};
+use super::navigation::collect_document_ids;
+use crate::error::{Error, Result};
+use crate::outline::Client as OutlineClient;
+
+/// Get all link tool definitions
+pub fn get_link_tools() -> Vec<Value> {
+    vec![
+        tool_definition(
+            "list_backlinks",
+            "List documents that link to a document",
+            &[
+                ("id", "string", "Document ID"),
+                ("limit", "number", "Number of documents (optional)"),
+            ],
+        ),
+        tool_definition(
+            "list_outgoing_links",
+            "List internal document links in a document, resolved to IDs",
+            &[("id", "string", "Document ID")],
+        ),
+        tool_definition(
+            "find_broken_links",
+            "Scan a collection for links to deleted or missing documents",
+            &[("collection_id", "string", "Collection ID")],
+        ),
+    ]
+}
+
+/// Call link tool
+pub async fn call_link_tool(name: &str, arguments: Value, client: &OutlineClient) -> Result<Value> {
+    match name {
+        "list_backlinks" => list_backlinks(arguments, client).await,
+        "list_outgoing_links" => list_outgoing_links(arguments, client).await,
+        "find_broken_links" => find_broken_links(arguments, client).await,
+        _ => unreachable!("Unknown link tool: {}", name),
+    }
+}
+
+async fn list_backlinks(args: Value, client: &OutlineClient) -> Result<Value> {
+    let id = get_string_arg(&args, "id")?;
+    let limit = get_optional_number_arg(&args, "limit");
+
+    debug!("Listing backlinks for document: {}", id);
+
+    let mut request_body = json!({ "backlinkDocumentId": id });
+    if let Some(lim) = limit {
+        request_body["limit"] = json!(lim);
+    }
+
+    let response = client.post("documents.list", request_body).await?;
+
+    Ok(create_mcp_success_response(
+        "Backlinks listed successfully",
+        Some(response),
+    ))
+}
+
+async fn list_outgoing_links(args: Value, client: &OutlineClient) -> Result<Value> {
+    let id = get_string_arg(&args, "id")?;
+
+    debug!("Listing outgoing links for document: {}", id);
+
+    let document = client.post("documents.info", json!({ "id": id })).await?;
+    let text = document["data"]["text"].as_str().unwrap_or_default();
+
+    let mut links = Vec::new();
+    for slug in extract_document_links(text, client.base_url().host_str()) {
+        let mut link = resolve_link(client, url_id_from_slug(&slug)).await?;
+        link["link"] = json!(format!("/doc/{slug}"));
+        links.push(link);
+    }
+
+    Ok(create_mcp_success_response(
+        "Outgoing links listed successfully",
+        Some(json!({ "data": links })),
+    ))
+}
+
+async fn find_broken_links(args: Value, client: &OutlineClient) -> Result<Value> {
+    let collection_id = get_string_arg(&args, "collection_id")?;
+
+    debug!("Finding broken links in collection: {}", collection_id);
+
+    let navigation = client
+        .post("collections.documents", json!({ "id": collection_id }))
+        .await?;
+    let nodes = navigation["data"].as_array().map_or(&[][..], Vec::as_slice);
+    let document_ids = collect_document_ids(nodes);
+
+    // Link targets are shared between documents, resolve each one only once
+    let mut resolved: HashMap<String, Value> = HashMap::new();
+    let mut broken = Vec::new();
+
+    for document_id in &document_ids {
+        let document = client
+            .post("documents.info", json!({ "id": document_id }))
+            .await?;
+        let text = document["data"]["text"].as_str().unwrap_or_default();
+
+        for slug in extract_document_links(text, client.base_url().host_str()) {
+            let url_id = url_id_from_slug(&slug);
+            if !resolved.contains_key(url_id) {
+                let link = resolve_link(client, url_id).await?;
+                resolved.insert(url_id.to_string(), link);
+            }
+
+            let link = &resolved[url_id];
+            if link["status"] != "ok" && link["status"] != "archived" {
+                broken.push(json!({
+                    "documentId": document_id,
+                    "documentTitle": document["data"]["title"],
+                    "link": format!("/doc/{slug}"),
+                    "status": link["status"]
+                }));
+            }
+        }
+    }
+
+    Ok(create_mcp_success_response(
+        "Broken links scan completed",
+        Some(json!({
+            "collectionId": collection_id,
+            "documentsScanned": document_ids.len(),
+            "data": broken
+        })),
+    ))
+}
+
+/// Resolve a document `urlId` to its ID, title and status
+///
+/// Status is one of `ok`, `archived`, `deleted` or `missing`.
+async fn resolve_link(client: &OutlineClient, url_id: &str) -> Result<Value> {
+    match client.post("documents.info", json!({ "id": url_id })).await {
+        Ok(response) => {
+            let data = &response["data"];
+            let status = if !data["deletedAt"].is_null() {
+                "deleted"
+            } else if !data["archivedAt"].is_null() {
+                "archived"
+            } else {
+                "ok"
+            };
+            Ok(json!({
+                "id": data["id"],
+                "title": data["title"],
+                "url": data["url"],
+                "status": status
+            }))
+        }
+        Err(Error::Api {
+            status: 400 | 404, ..
+        }) => Ok(json!({ "id": null, "status": "missing" })),
+        Err(e) => Err(e),
+    }
+}
+
+/// Extract unique internal document slugs (the part after `/doc/`) from markdown
+///
+/// Relative links and absolute links to `outline_host` count as internal,
+/// `/doc/` paths on other hosts do not. Code blocks and code spans are skipped.
+fn extract_document_links(text: &str, outline_host: Option<&str>) -> Vec<String> {
+    let prose = outside_code(text);
+    let mut slugs: Vec<String> = Vec::new();
+
+    for (pos, _) in prose.match_indices("/doc/") {
+        // The URL starts after the delimiter preceding `/doc/`
+        let prefix_start = prose[..pos]
+            .rfind(|c: char| c.is_whitespace() || "([<\"'".contains(c))
+            .map_or(0, |i| i + 1);
+        if !is_internal_prefix(&prose[prefix_start..pos], outline_host) {
+            continue;
+        }
+
+        let rest = &prose[pos + "/doc/".len()..];
+        let slug: String = rest
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+            .collect();
+
+        if !slug.is_empty() && !slugs.contains(&slug) {
+            slugs.push(slug);
+        }
+    }
+
+    slugs
+}
+
+/// Whether the text before `/doc/` makes a link into this Outline instance
+///
+/// Empty means a relative link; otherwise it must be the scheme and host of
+/// `outline_host`. Outline cloud serves its API from `app.getoutline.com`
+/// and documents from team subdomains, which count as well.
+fn is_internal_prefix(prefix: &str, outline_host: Option<&str>) -> bool {
+    if prefix.is_empty() {
+        return true;
+    }
+    let Some(host) = prefix
+        .strip_prefix("https://")
+        .or_else(|| prefix.strip_prefix("http://"))
+    else {
+        return false;
+    };
+    let host = host.split(':').next().unwrap_or_default().to_lowercase();
+    outline_host.is_some_and(|outline| {
+        let outline = outline.to_lowercase();
+        host == outline
+            || outline
+                .strip_prefix("app.")
+                .filter(|domain| *domain == "getoutline.com")
+                .is_some_and(|domain| host.ends_with(&format!(".{domain}")))
+    })
+}
+
+/// `text` with fenced code blocks and inline code spans blanked out
+fn outside_code(text: &str) -> String {
+    let mut prose = String::with_capacity(text.len());
+    let mut in_fence = false;
+
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            prose.push('\n');
+            continue;
+        }
+        if in_fence {
+            prose.push('\n');
+            continue;
+        }
+
+        let mut in_code = false;
+        for c in line.chars() {
+            if c == '`' {
+                in_code = !in_code;
+            }
+            prose.push(if in_code || c == '`' { ' ' } else { c });
+        }
+    }
+
+    prose
+}
+
+/// Extract the `urlId` from a document slug such as `my-title-AbC123xYz9`
+fn url_id_from_slug(slug: &str) -> &str {
+    slug.rsplit('-').next().unwrap_or(slug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_document_links() {
+        let text = "See [Setup](/doc/setup-guide-AbC123xYz9) and \
+                    [Deploy](https://wiki.example.com/doc/deploy-Qw12Er34Ty#step-2). \
+                    Again [Setup](/doc/setup-guide-AbC123xYz9), external [x](https://example.com/docs/a)";
+
+        let links = extract_document_links(text, Some("wiki.example.com"));
+        assert_eq!(links, ["setup-guide-AbC123xYz9", "deploy-Qw12Er34Ty"]);
+    }
+
+    #[test]
+    fn test_foreign_hosts_and_code_ignored() {
+        let text = "[Readme](https://github.com/acme/app/doc/readme-Zz99Yy88Xx) \
+                    `see /doc/inline-Aa11Bb22Cc`\n\
+                    ```\n[Old](/doc/fenced-Dd33Ee44Ff)\n```\n\
+                    [Team](https://acme.getoutline.com/doc/team-Gg55Hh66Ii)";
+
+        assert!(extract_document_links(text, Some("wiki.example.com")).is_empty());
+        assert_eq!(
+            extract_document_links(text, Some("app.getoutline.com")),
+            ["team-Gg55Hh66Ii"]
+        );
+    }
+
+    #[test]
+    fn test_url_id_from_slug() {
+        assert_eq!(url_id_from_slug("setup-guide-AbC123xYz9"), "AbC123xYz9");
+        assert_eq!(url_id_from_slug("AbC123xYz9"), "AbC123xYz9");
+    }
+}
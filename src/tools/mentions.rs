@@ -0,0 +1,400 @@
+//! @mention resolution for agent-written markdown
+//!
+//! Turns `@email`, `@Name` and `@"Full Name"` into Outline mention links of the
+//! form `@[Name](mention://<id>/user/<userId>)`, which Outline's markdown parser
+//! converts into mention nodes that notify the user. Code spans and fenced
+//! code blocks are left untouched.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+
+use super::common::get_optional_bool_arg;
+use crate::outline::Client as OutlineClient;
+
+/// `resolve_mentions` parameter of the tools writing markdown
+pub const MENTIONS_PARAM: (&str, &str, &str) = (
+    "resolve_mentions",
+    "boolean",
+    "Turn @Name, @\"Full Name\" and @email into mentions that notify users, default false (optional)",
+);
+
+/// Users fetched per mention lookup
+const USER_LOOKUP_LIMIT: usize = 25;
+
+/// Distinct mentions looked up per text; the rest stay unresolved
+const MAX_MENTION_LOOKUPS: usize = 10;
+
+/// Result of resolving the mentions in a piece of markdown
+#[derive(Debug)]
+pub struct ResolvedMentions {
+    /// Markdown with resolved mentions replaced by mention links
+    pub text: String,
+    /// `{mention, userId, name}` for every resolved mention
+    pub resolved: Vec<Value>,
+    /// Mentions that did not match exactly one user
+    pub unresolved: Vec<String>,
+}
+
+impl ResolvedMentions {
+    /// Attach mention details to a tool response when there are any
+    pub fn annotate(&self, response: &mut Value) {
+        if !self.resolved.is_empty() {
+            response["mentions"] = json!(self.resolved);
+        }
+        if !self.unresolved.is_empty() {
+            response["unresolvedMentions"] = json!(self.unresolved);
+        }
+    }
+}
+
+/// Resolve mentions in `text` when the tool arguments opt in
+pub async fn resolve_mentions_in_args(
+    args: &Value,
+    client: &OutlineClient,
+    text: String,
+) -> (String, Option<ResolvedMentions>) {
+    if !get_optional_bool_arg(args, "resolve_mentions").unwrap_or(false) {
+        return (text, None);
+    }
+
+    let mut resolved = resolve_mentions(client, &text).await;
+    let text = std::mem::take(&mut resolved.text);
+    (text, Some(resolved))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MentionToken {
+    start: usize,
+    end: usize,
+    query: String,
+    is_email: bool,
+}
+
+/// Resolve all mentions in `text` through `users.list`
+///
+/// Text without mentions is returned unchanged without any API calls. Failed
+/// lookups leave the mentions unresolved rather than failing the write.
+pub async fn resolve_mentions(client: &OutlineClient, text: &str) -> ResolvedMentions {
+    let tokens = find_mentions(text);
+    let mut users: HashMap<String, Option<(String, String)>> = HashMap::new();
+
+    for token in &tokens {
+        let key = token.query.to_lowercase();
+        if users.contains_key(&key) {
+            continue;
+        }
+        if users.len() >= MAX_MENTION_LOOKUPS {
+            debug!(
+                "Mention lookup limit reached, leaving @{} unresolved",
+                token.query
+            );
+            break;
+        }
+
+        debug!("Resolving mention: @{}", token.query);
+
+        let response = match client
+            .post(
+                "users.list",
+                json!({ "query": token.query, "limit": USER_LOOKUP_LIMIT }),
+            )
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                warn!("Mention lookup failed, leaving mentions unresolved: {}", e);
+                break;
+            }
+        };
+        let candidates = response["data"].as_array().cloned().unwrap_or_default();
+        let user = match_user(&token.query, token.is_email, &candidates).and_then(|user| {
+            Some((
+                user["id"].as_str()?.to_string(),
+                user["name"].as_str()?.to_string(),
+            ))
+        });
+        users.insert(key, user);
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut resolved = Vec::new();
+    let mut unresolved = Vec::new();
+    let mut last = 0;
+
+    for token in &tokens {
+        let mention = &text[token.start..token.end];
+        output.push_str(&text[last..token.start]);
+        if let Some((user_id, name)) = users
+            .get(&token.query.to_lowercase())
+            .and_then(Option::as_ref)
+        {
+            output.push_str(&mention_link(name, user_id));
+            resolved.push(json!({
+                "mention": mention,
+                "userId": user_id,
+                "name": name
+            }));
+        } else {
+            output.push_str(mention);
+            unresolved.push(mention.to_string());
+        }
+        last = token.end;
+    }
+    output.push_str(&text[last..]);
+
+    ResolvedMentions {
+        text: output,
+        resolved,
+        unresolved,
+    }
+}
+
+fn mention_link(name: &str, user_id: &str) -> String {
+    let label = name.replace(['[', ']'], "");
+    format!(
+        "@[{label}](mention://{}/user/{user_id})",
+        uuid::Uuid::new_v4()
+    )
+}
+
+/// Pick the user a mention refers to
+///
+/// Emails must match exactly. Names match the full name, or the first name
+/// when exactly one user has it.
+fn match_user<'a>(query: &str, is_email: bool, users: &'a [Value]) -> Option<&'a Value> {
+    let field_matches = |user: &Value, field: &str| {
+        user[field]
+            .as_str()
+            .is_some_and(|value| value.eq_ignore_ascii_case(query))
+    };
+
+    if is_email {
+        return users.iter().find(|user| field_matches(user, "email"));
+    }
+
+    if let Some(user) = users.iter().find(|user| field_matches(user, "name")) {
+        return Some(user);
+    }
+
+    let mut by_first_name = users.iter().filter(|user| {
+        user["name"]
+            .as_str()
+            .and_then(|name| name.split_whitespace().next())
+            .is_some_and(|first| first.eq_ignore_ascii_case(query))
+    });
+    match (by_first_name.next(), by_first_name.next()) {
+        (Some(user), None) => Some(user),
+        _ => None,
+    }
+}
+
+/// Find mention candidates outside of code
+fn find_mentions(text: &str) -> Vec<MentionToken> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    let mut in_fence = false;
+
+    for line in text.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let mut in_code = false;
+        let mut previous: Option<char> = None;
+        let mut chars = line.char_indices().peekable();
+
+        while let Some((index, c)) = chars.next() {
+            if c == '`' {
+                in_code = !in_code;
+            } else if c == '@'
+                && !in_code
+                && !previous.is_some_and(|p| p.is_alphanumeric() || "._+-@[".contains(p))
+            {
+                if let Some(token) = parse_mention(line, index) {
+                    // Skip past the mention so its characters are not rescanned
+                    while chars.peek().is_some_and(|(i, _)| *i < token.end) {
+                        chars.next();
+                    }
+                    previous = line[..token.end].chars().next_back();
+                    tokens.push(MentionToken {
+                        start: line_start + token.start,
+                        end: line_start + token.end,
+                        ..token
+                    });
+                    continue;
+                }
+            }
+            previous = Some(c);
+        }
+    }
+
+    tokens
+}
+
+/// Parse a mention starting with the `@` at `at` within `line`
+fn parse_mention(line: &str, at: usize) -> Option<MentionToken> {
+    let rest = &line[at + 1..];
+
+    if let Some(quoted) = rest.strip_prefix('"') {
+        let close = quoted.find('"')?;
+        let name = quoted[..close].trim();
+        if name.is_empty() || name.contains('\n') {
+            return None;
+        }
+        return Some(MentionToken {
+            start: at,
+            end: at + 1 + close + 2,
+            query: name.to_string(),
+            is_email: false,
+        });
+    }
+
+    let length = rest
+        .char_indices()
+        .find(|(_, c)| !(c.is_alphanumeric() || "._+-@".contains(*c)))
+        .map_or(rest.len(), |(i, _)| i);
+    let token = rest[..length].trim_end_matches(['.', '-', '_']);
+    if token.is_empty() {
+        return None;
+    }
+
+    let is_email = match token.split_once('@') {
+        Some((local, domain)) => {
+            if local.is_empty() || !domain.contains('.') || domain.contains('@') {
+                return None;
+            }
+            true
+        }
+        None => false,
+    };
+
+    Some(MentionToken {
+        start: at,
+        end: at + 1 + token.len(),
+        query: token.to_string(),
+        is_email,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::stand_in;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn queries(text: &str) -> Vec<(String, bool)> {
+        find_mentions(text)
+            .into_iter()
+            .map(|t| (t.query, t.is_email))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_mentions() {
+        assert_eq!(
+            queries("Hi @ann, ping @bob.smith@example.com and @\"Jane Doe\"."),
+            vec![
+                ("ann".to_string(), false),
+                ("bob.smith@example.com".to_string(), true),
+                ("Jane Doe".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_mentions_skips_code_and_addresses() {
+        let text =
+            "mail me@example.com\n`@inline` @[Ann](mention://x/user/y)\n```\n@fenced\n```\n@after";
+        assert_eq!(queries(text), vec![("after".to_string(), false)]);
+    }
+
+    #[test]
+    fn test_find_mentions_offsets() {
+        let text = "ñ @ann!";
+        let token = &find_mentions(text)[0];
+        assert_eq!(&text[token.start..token.end], "@ann");
+    }
+
+    #[tokio::test]
+    async fn test_mentions_are_opt_in() {
+        let client = stand_in::serve(Vec::new()).await;
+
+        let (text, resolved) =
+            resolve_mentions_in_args(&json!({}), &client, "Hi @ann".into()).await;
+        assert_eq!(text, "Hi @ann");
+        assert!(resolved.is_none());
+
+        let args = json!({ "resolve_mentions": true });
+        let (_, resolved) = resolve_mentions_in_args(&args, &client, "Hi @ann".into()).await;
+        assert!(resolved.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_failed_lookup_leaves_mentions_unresolved() {
+        // No users.list route: every lookup fails
+        let client = stand_in::serve(Vec::new()).await;
+
+        let resolved = resolve_mentions(&client, "Thanks @ann").await;
+        assert_eq!(resolved.text, "Thanks @ann");
+        assert_eq!(resolved.unresolved, ["@ann"]);
+    }
+
+    #[tokio::test]
+    async fn test_lookups_are_deduplicated_and_bounded() {
+        let lookups = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&lookups);
+        let client = stand_in::serve(vec![(
+            "users.list",
+            Arc::new(move |_: &Value| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                stand_in::ok(&json!({ "data": [] }))
+            }),
+        )])
+        .await;
+
+        let others: Vec<String> = (0..MAX_MENTION_LOOKUPS + 5)
+            .map(|i| format!("@user{i}"))
+            .collect();
+        let text = format!("@Ann @ann @ANN {}", others.join(" "));
+        let resolved = resolve_mentions(&client, &text).await;
+        assert_eq!(lookups.load(Ordering::SeqCst), MAX_MENTION_LOOKUPS);
+        assert_eq!(resolved.text, text);
+    }
+
+    #[test]
+    fn test_match_user() {
+        let users = vec![
+            json!({ "id": "1", "name": "Ann Lee", "email": "ann@example.com" }),
+            json!({ "id": "2", "name": "Bob Stone" }),
+            json!({ "id": "3", "name": "Bob Marsh" }),
+        ];
+
+        assert_eq!(
+            match_user("ANN@example.com", true, &users).unwrap()["id"],
+            "1"
+        );
+        assert_eq!(match_user("ann lee", false, &users).unwrap()["id"], "1");
+        assert_eq!(match_user("Ann", false, &users).unwrap()["id"], "1");
+        // Ambiguous first name
+        assert!(match_user("Bob", false, &users).is_none());
+        assert!(match_user("Bob Marsh", false, &users).is_some());
+    }
+
+    #[test]
+    fn test_mention_link() {
+        let link = mention_link("Ann [Ops]", "user-1");
+        assert!(link.starts_with("@[Ann Ops](mention://"));
+        assert!(link.ends_with("/user/user-1)"));
+    }
+}
@@ -5,51 +5,176 @@
 use serde_json::Value;
 
 use crate::error::Result;
+use crate::mcp::Transport;
 use crate::outline::Client as OutlineClient;
 
 // Submodules
+mod analytics;
+mod bulk;
+mod chunks;
 mod collections;
 mod comments;
 mod common;
 mod documents;
+mod events;
+mod exports;
+mod formatting;
+mod imports;
+mod links;
+mod mentions;
+mod navigation;
+mod output;
+mod pins;
+#[cfg(test)]
+mod stand_in;
+mod stars;
+mod subscriptions;
+mod sync;
+mod templates;
 mod users;
 
-/// Get list of all available tools
+/// Tools that access the local filesystem and are hidden outside of STDIO mode
+const LOCAL_ONLY_TOOLS: &[&str] = &["sync_collection"];
+
+/// Get list of all tools available for the transport
 #[allow(clippy::too_many_lines)]
-pub fn get_tools_list() -> Vec<Value> {
+pub fn get_tools_list(transport: Transport) -> Vec<Value> {
     let mut tools = Vec::new();
 
     // Document tools
     tools.extend(documents::get_document_tools());
 
+    // Template tools
+    tools.extend(templates::get_template_tools());
+
+    // Bulk document tools
+    tools.extend(bulk::get_bulk_tools());
+
+    // Export tools
+    tools.extend(exports::get_export_tools());
+
+    // Import tools
+    tools.extend(imports::get_import_tools());
+
     // Collection tools
     tools.extend(collections::get_collection_tools());
 
     // Comment tools
     tools.extend(comments::get_comment_tools());
 
+    // Link tools
+    tools.extend(links::get_link_tools());
+
+    // Event tools
+    tools.extend(events::get_event_tools());
+
+    // Analytics tools
+    tools.extend(analytics::get_analytics_tools());
+
+    // Star tools
+    tools.extend(stars::get_star_tools());
+
+    // Pin tools
+    tools.extend(pins::get_pin_tools());
+
+    // Subscription tools
+    tools.extend(subscriptions::get_subscription_tools());
+
     // User tools
     tools.extend(users::get_user_tools());
 
+    // Local sync tools
+    tools.extend(sync::get_sync_tools());
+
+    if !transport.allows_local_files() {
+        tools.retain(|tool| {
+            tool["name"]
+                .as_str()
+                .is_some_and(|name| !LOCAL_ONLY_TOOLS.contains(&name))
+        });
+    }
+
+    for tool in &mut tools {
+        if tool["name"]
+            .as_str()
+            .is_some_and(|name| !formatting::PLAIN_TEXT_TOOLS.contains(&name))
+        {
+            common::add_optional_param(tool, formatting::FORMAT_PARAM);
+            common::add_optional_param(tool, output::FIELDS_PARAM);
+        }
+    }
+
     tools
 }
 
 /// Call tool by name
-pub async fn call_tool(name: &str, arguments: Value, client: &OutlineClient) -> Result<Value> {
+///
+/// Document bodies longer than `max_result_chars` are truncated, except in
+/// `get_document_chunk` which exists to read them piece by piece.
+#[allow(clippy::too_many_lines)]
+pub async fn call_tool(
+    name: &str,
+    arguments: Value,
+    client: &OutlineClient,
+    transport: Transport,
+    max_result_chars: Option<usize>,
+) -> Result<Value> {
+    // Plain-text tools have no `format`/`fields` parameters of their own, and
+    // the export tools use `format` for the file type
+    let plain_text = formatting::PLAIN_TEXT_TOOLS.contains(&name);
+    let format = if plain_text {
+        formatting::ResponseFormat::Markdown
+    } else {
+        match formatting::ResponseFormat::from_args(&arguments, name) {
+            Ok(format) => format,
+            Err(error) => return Ok(common::handle_tool_error(&error)),
+        }
+    };
+    let fields = if plain_text {
+        None
+    } else {
+        output::fields_from_args(&arguments)
+    };
+
+    let started = std::time::Instant::now();
     let result = match name {
         // Document tools
         "create_document"
         | "get_document"
         | "update_document"
+        | "unpublish_document"
         | "delete_document"
         | "list_documents"
         | "search_documents"
+        | "ask_workspace"
         | "archive_document"
         | "move_document"
         | "create_template_from_document"
         | "restore_document"
         | "unarchive_document"
-        | "list_drafts" => documents::call_document_tool(name, arguments, client).await,
+        | "list_drafts"
+        | "list_child_documents"
+        | "get_document_path"
+        | "get_document_chunk" => documents::call_document_tool(name, arguments, client).await,
+
+        // Template tools
+        "list_templates" | "get_template" | "create_document_from_template" => {
+            templates::call_template_tool(name, arguments, client).await
+        }
+
+        // Bulk document tools
+        "bulk_move_documents"
+        | "bulk_archive_documents"
+        | "bulk_delete_documents"
+        | "bulk_update_documents" => bulk::call_bulk_tool(name, arguments, client).await,
+
+        // Export tools
+        "export_document" | "export_collection" => {
+            exports::call_export_tool(name, arguments, client, transport).await
+        }
+
+        // Import tools
+        "import_document" => imports::call_import_tool(name, arguments, client, transport).await,
 
         // Collection tools
         "create_collection"
@@ -57,20 +182,57 @@ pub async fn call_tool(name: &str, arguments: Value, client: &OutlineClient) ->
         | "update_collection"
         | "list_collections"
         | "delete_collection"
-        | "get_collection_documents" => {
-            collections::call_collection_tool(name, arguments, client).await
-        }
+        | "get_collection_documents"
+        | "get_collection_tree" => collections::call_collection_tool(name, arguments, client).await,
 
         // Comment tools
         "create_comment"
         | "update_comment"
         | "delete_comment"
+        | "add_comment_reaction"
+        | "remove_comment_reaction"
+        | "resolve_comment"
+        | "unresolve_comment"
         | "list_document_comments"
+        | "get_document_discussion"
         | "get_comment" => comments::call_comment_tool(name, arguments, client).await,
 
+        // Link tools
+        "list_backlinks" | "list_outgoing_links" | "find_broken_links" => {
+            links::call_link_tool(name, arguments, client).await
+        }
+
+        // Event tools
+        "list_events" | "get_recent_activity" => {
+            events::call_event_tool(name, arguments, client).await
+        }
+
+        // Analytics tools
+        "get_document_views" | "find_stale_documents" | "find_orphan_documents" => {
+            analytics::call_analytics_tool(name, arguments, client).await
+        }
+
+        // Star tools
+        "create_star" | "delete_star" | "list_stars" => {
+            stars::call_star_tool(name, arguments, client).await
+        }
+
+        // Pin tools
+        "create_pin" | "update_pin" | "delete_pin" | "list_pins" => {
+            pins::call_pin_tool(name, arguments, client).await
+        }
+
+        // Subscription tools
+        "subscribe_to_document" | "unsubscribe_from_document" => {
+            subscriptions::call_subscription_tool(name, arguments, client).await
+        }
+
         // User tools
         "list_users" | "get_user" => users::call_user_tool(name, arguments, client).await,
 
+        // Local sync tools
+        "sync_collection" => sync::call_sync_tool(name, arguments, client, transport).await,
+
         // Unknown tool - return MCP-compliant error
         _ => {
             return Ok(common::create_mcp_error_response(&format!(
@@ -79,9 +241,28 @@ pub async fn call_tool(name: &str, arguments: Value, client: &OutlineClient) ->
         }
     };
 
+    crate::metrics::global().observe_tool(name, result.is_err(), started.elapsed());
+
     // Handle tool execution errors by converting them to MCP error responses
     match result {
-        Ok(success_response) => Ok(success_response),
+        Ok(mut success_response) => {
+            if let Some(fields) = &fields {
+                output::project_response(&mut success_response, fields);
+            }
+            if let Some(max_chars) = max_result_chars.filter(|_| name != "get_document_chunk") {
+                output::limit_response(&mut success_response, max_chars);
+            }
+            // Plain-text tools render their own content, e.g. an archive resource
+            if plain_text {
+                return Ok(success_response);
+            }
+            Ok(formatting::format_response(
+                name,
+                format,
+                fields.as_deref(),
+                success_response,
+            ))
+        }
         Err(error) => Ok(common::handle_tool_error(&error)),
     }
 }
@@ -89,11 +270,12 @@ pub async fn call_tool(name: &str, arguments: Value, client: &OutlineClient) ->
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::json;
 
     #[test]
     fn test_get_tools_list() {
-        let tools = get_tools_list();
-        assert_eq!(tools.len(), 25);
+        let tools = get_tools_list(Transport::Stdio);
+        assert_eq!(tools.len(), 64);
 
         // Check first tool is a document tool
         let first_tool = &tools[0];
@@ -103,4 +285,110 @@ mod tests {
             .unwrap()
             .contains("Create"));
     }
+
+    #[test]
+    fn test_local_only_tools_hidden_in_http_mode() {
+        let tools = get_tools_list(Transport::Http);
+        assert_eq!(tools.len(), 63);
+        assert!(tools.iter().all(|t| t["name"] != "sync_collection"));
+    }
+
+    #[test]
+    fn test_resolve_mentions_never_required() {
+        for tool in get_tools_list(Transport::Stdio) {
+            let required = tool["inputSchema"]["required"].as_array().unwrap();
+            assert!(
+                !required.iter().any(|r| r == "resolve_mentions"),
+                "{}",
+                tool["name"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_array_params_declare_items() {
+        for tool in get_tools_list(Transport::Stdio) {
+            let properties = tool["inputSchema"]["properties"].as_object().unwrap();
+            for (name, schema) in properties {
+                if schema["type"] == "array" {
+                    assert!(schema["items"].is_object(), "{}.{name}", tool["name"]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_param_on_structured_tools() {
+        let tools = get_tools_list(Transport::Stdio);
+        let format_param = |name: &str| {
+            let tool = tools.iter().find(|t| t["name"] == name).unwrap();
+            tool["inputSchema"]["properties"].get("format").is_some()
+        };
+
+        assert!(format_param("list_documents"));
+        assert!(!format_param("get_collection_tree"));
+
+        let list_documents = tools
+            .iter()
+            .find(|t| t["name"] == "list_documents")
+            .unwrap();
+        let required = list_documents["inputSchema"]["required"]
+            .as_array()
+            .unwrap();
+        assert!(!required.iter().any(|r| r == "format"));
+    }
+
+    #[tokio::test]
+    async fn test_export_format_not_taken_for_response_format() {
+        let client = stand_in::serve(vec![(
+            "documents.export",
+            std::sync::Arc::new(|_: &Value| (200, "text/html", b"<h1>Plan</h1>".to_vec())),
+        )])
+        .await;
+
+        let result = call_tool(
+            "export_document",
+            serde_json::json!({ "id": "doc-1", "format": "html" }),
+            &client,
+            Transport::Http,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_ne!(result["isError"], true, "{result}");
+        assert_eq!(result["content"][0]["text"], "<h1>Plan</h1>");
+    }
+
+    #[tokio::test]
+    async fn test_export_collection_keeps_archive_resource() {
+        let client = stand_in::serve(vec![
+            (
+                "collections.export",
+                stand_in::fixed(json!({ "data": { "fileOperation": { "id": "op-1" } } })),
+            ),
+            (
+                "fileOperations.info",
+                stand_in::fixed(json!({
+                    "data": { "id": "op-1", "state": "complete", "name": "docs.zip", "size": 3 }
+                })),
+            ),
+            (
+                "fileOperations.redirect",
+                std::sync::Arc::new(|_: &Value| (200, "application/zip", b"zip".to_vec())),
+            ),
+        ])
+        .await;
+
+        let result = call_tool(
+            "export_collection",
+            json!({ "collection_id": "col-1" }),
+            &client,
+            Transport::Http,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result["content"][1]["type"], "resource", "{result}");
+        assert_eq!(result["content"][1]["resource"]["blob"], "emlw");
+    }
 }
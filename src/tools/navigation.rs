@@ -0,0 +1,148 @@
+//! Collection navigation tree helpers
+//!
+//! Walks and renders the nested `navigation` structure returned by `collections.documents`.
+
+use std::fmt::Write;
+
+use serde_json::Value;
+
+/// Get children of a navigation node as a slice
+fn children(node: &Value) -> &[Value] {
+    node["children"].as_array().map_or(&[][..], Vec::as_slice)
+}
+
+/// Find the chain of navigation nodes leading from a root node to the document
+pub fn find_navigation_path<'a>(nodes: &'a [Value], id: &str) -> Option<Vec<&'a Value>> {
+    for node in nodes {
+        if node["id"].as_str() == Some(id) {
+            return Some(vec![node]);
+        }
+
+        if let Some(mut path) = find_navigation_path(children(node), id) {
+            path.insert(0, node);
+            return Some(path);
+        }
+    }
+
+    None
+}
+
+/// Find a single navigation node by document ID
+pub fn find_navigation_node<'a>(nodes: &'a [Value], id: &str) -> Option<&'a Value> {
+    find_navigation_path(nodes, id).and_then(|path| path.last().copied())
+}
+
+/// Collect IDs of all documents in the navigation tree (depth-first)
+pub fn collect_document_ids(nodes: &[Value]) -> Vec<String> {
+    let mut ids = Vec::new();
+    for node in nodes {
+        if let Some(id) = node["id"].as_str() {
+            ids.push(id.to_string());
+        }
+        ids.extend(collect_document_ids(children(node)));
+    }
+    ids
+}
+
+/// Render navigation nodes as an indented markdown outline
+///
+/// `max_depth` limits how many levels are rendered (1 = top level only).
+/// `filter` keeps nodes whose title contains the text (case-insensitive)
+/// together with their ancestors so the hierarchy stays readable.
+pub fn render_outline(nodes: &[Value], max_depth: Option<usize>, filter: Option<&str>) -> String {
+    let filter = filter.map(str::to_lowercase);
+    let mut output = String::new();
+    render_nodes(nodes, 0, max_depth, filter.as_deref(), &mut output);
+    output
+}
+
+fn render_nodes(
+    nodes: &[Value],
+    depth: usize,
+    max_depth: Option<usize>,
+    filter: Option<&str>,
+    output: &mut String,
+) {
+    if max_depth.is_some_and(|max| depth >= max) {
+        return;
+    }
+
+    for node in nodes {
+        if let Some(f) = filter {
+            if !matches_filter(node, f) {
+                continue;
+            }
+        }
+
+        let title = node["title"].as_str().unwrap_or("Untitled");
+        let id = node["id"].as_str().unwrap_or_default();
+        let url = node["url"].as_str().unwrap_or_default();
+
+        output.push_str(&"  ".repeat(depth));
+        let _ = writeln!(output, "- [{title}]({url}) `{id}`");
+
+        render_nodes(children(node), depth + 1, max_depth, filter, output);
+    }
+}
+
+/// Check whether the node or any of its descendants has a matching title
+fn matches_filter(node: &Value, filter: &str) -> bool {
+    node["title"]
+        .as_str()
+        .is_some_and(|title| title.to_lowercase().contains(filter))
+        || children(node)
+            .iter()
+            .any(|child| matches_filter(child, filter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_tree() -> Value {
+        json!([
+            { "id": "a", "title": "Alpha", "url": "/doc/alpha", "children": [] },
+            { "id": "b", "title": "Beta", "url": "/doc/beta", "children": [
+                { "id": "c", "title": "Gamma", "url": "/doc/gamma", "children": [
+                    { "id": "d", "title": "Delta", "url": "/doc/delta", "children": [] }
+                ]}
+            ]}
+        ])
+    }
+
+    #[test]
+    fn test_find_navigation_path() {
+        let tree = sample_tree();
+        let nodes = tree.as_array().unwrap();
+
+        let path = find_navigation_path(nodes, "d").unwrap();
+        let titles: Vec<&str> = path.iter().map(|n| n["title"].as_str().unwrap()).collect();
+        assert_eq!(titles, ["Beta", "Gamma", "Delta"]);
+
+        assert_eq!(find_navigation_path(nodes, "a").unwrap().len(), 1);
+        assert!(find_navigation_path(nodes, "missing").is_none());
+        assert_eq!(find_navigation_node(nodes, "c").unwrap()["title"], "Gamma");
+        assert_eq!(collect_document_ids(nodes), ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_render_outline() {
+        let tree = sample_tree();
+        let nodes = tree.as_array().unwrap();
+
+        let full = render_outline(nodes, None, None);
+        assert_eq!(full.lines().count(), 4);
+        assert!(full.contains("    - [Delta](/doc/delta) `d`"));
+
+        let shallow = render_outline(nodes, Some(1), None);
+        assert_eq!(
+            shallow,
+            "- [Alpha](/doc/alpha) `a`\n- [Beta](/doc/beta) `b`\n"
+        );
+
+        let filtered = render_outline(nodes, None, Some("delta"));
+        assert_eq!(filtered.lines().count(), 3);
+        assert!(!filtered.contains("Alpha"));
+    }
+}
@@ -0,0 +1,226 @@
+//! Result shaping shared by all tools
+//!
+//! The `fields` projection and the server-wide limit on document body size,
+//! applied to `structuredContent` before a result is formatted.
+
+use serde_json::{json, Map, Value};
+
+use super::chunks::split_by_bytes;
+use super::common::get_optional_string_array_arg;
+
+/// `fields` parameter added to every tool with a structured result
+pub const FIELDS_PARAM: (&str, &str, &str) = (
+    "fields",
+    "array",
+    "Only return these fields of each item, e.g. [\"id\",\"title\",\"url\"]; dotted paths like createdBy.name are allowed (optional)",
+);
+
+/// Requested `fields`, `None` when the projection is not used
+pub fn fields_from_args(args: &Value) -> Option<Vec<String>> {
+    get_optional_string_array_arg(args, FIELDS_PARAM.0).filter(|fields| !fields.is_empty())
+}
+
+/// Keep only `fields` of the items in `structuredContent.data`
+pub fn project_response(response: &mut Value, fields: &[String]) {
+    let Some(content) = response
+        .get_mut("structuredContent")
+        .and_then(Value::as_object_mut)
+    else {
+        return;
+    };
+
+    content.remove("policies");
+    match content.get_mut("data") {
+        Some(Value::Array(items)) => {
+            for item in items {
+                *item = project(item, fields);
+            }
+        }
+        Some(item @ Value::Object(_)) => *item = project(item, fields),
+        _ => {}
+    }
+}
+
+/// Copy the values at the dotted `fields` paths into a new object
+fn project(item: &Value, fields: &[String]) -> Value {
+    let mut projected = Map::new();
+
+    for field in fields {
+        let pointer = format!("/{}", field.replace('.', "/"));
+        let Some(value) = item.pointer(&pointer) else {
+            continue;
+        };
+
+        let mut parts = field.split('.').peekable();
+        let mut target = &mut projected;
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                target.insert(part.to_string(), value.clone());
+                break;
+            }
+            let entry = target
+                .entry(part.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            let Value::Object(next) = entry else {
+                break;
+            };
+            target = next;
+        }
+    }
+
+    Value::Object(projected)
+}
+
+/// Cut long strings in `structuredContent.data` to `max_chars`
+///
+/// Document bodies, including those inside search results, end with a marker
+/// naming the total size and pointing to `get_document_chunk`; any other
+/// oversized string, such as a search `context`, gets a plain marker. The
+/// response gets `"truncated": true` when anything was cut.
+pub fn limit_response(response: &mut Value, max_chars: usize) {
+    let Some(content) = response.get_mut("structuredContent") else {
+        return;
+    };
+
+    if content.get("data").is_some() && limit_strings(&mut content["data"], max_chars) {
+        content["truncated"] = json!(true);
+    }
+}
+
+fn limit_strings(value: &mut Value, max_chars: usize) -> bool {
+    match value {
+        Value::Object(map) => {
+            let id = map.get("id").and_then(Value::as_str).map(String::from);
+            let mut truncated = false;
+            for (key, field) in map.iter_mut() {
+                if let Value::String(text) = field {
+                    let document_id = id.as_deref().filter(|_| key == "text");
+                    if let Some(cut) = truncate_text(text, max_chars, document_id) {
+                        *text = cut;
+                        truncated = true;
+                    }
+                } else {
+                    truncated |= limit_strings(field, max_chars);
+                }
+            }
+            truncated
+        }
+        Value::Array(items) => items.iter_mut().fold(false, |truncated, item| {
+            limit_strings(item, max_chars) | truncated
+        }),
+        _ => false,
+    }
+}
+
+/// `text` cut to `max_chars` with a marker, `None` when it is short enough
+///
+/// Document bodies are cut where `get_document_chunk` with `split_by: "bytes"`
+/// ends its first chunk, so the marker can name the chunks holding the rest.
+fn truncate_text(text: &str, max_chars: usize, document_id: Option<&str>) -> Option<String> {
+    let (end, _) = text.char_indices().nth(max_chars)?;
+    let total = text.chars().count();
+
+    let Some(id) = document_id else {
+        return Some(format!(
+            "{}\n\n[… truncated after {max_chars} of {total} characters]",
+            &text[..end]
+        ));
+    };
+
+    let chunks = split_by_bytes(text, end);
+    let cut = chunks[0].end;
+    let shown = text[..cut].chars().count();
+    let rest = match chunks.len() {
+        2 => "chunk 2".to_string(),
+        count => format!("chunks 2 to {count}"),
+    };
+    Some(format!(
+        "{}\n\n[… truncated after {shown} of {total} characters; read the rest with \
+         get_document_chunk using id {id}, split_by \"bytes\", chunk_size {end} \
+         and {rest}]",
+        &text[..cut]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_from_args() {
+        assert_eq!(
+            fields_from_args(&json!({ "fields": ["id", "title"] })),
+            Some(vec!["id".to_string(), "title".to_string()])
+        );
+        assert_eq!(fields_from_args(&json!({ "fields": [] })), None);
+        assert_eq!(fields_from_args(&json!({})), None);
+    }
+
+    #[test]
+    fn test_project_response() {
+        let mut response = json!({
+            "structuredContent": {
+                "data": [
+                    { "id": "1", "title": "A", "text": "body", "createdBy": { "id": "u", "name": "Ann" } },
+                    { "id": "2", "title": "B", "text": "body" }
+                ],
+                "pagination": { "limit": 2 },
+                "policies": [{ "id": "1" }]
+            }
+        });
+        let fields = ["id", "createdBy.name", "missing"].map(String::from);
+        project_response(&mut response, &fields);
+
+        let content = &response["structuredContent"];
+        assert_eq!(
+            content["data"][0],
+            json!({ "id": "1", "createdBy": { "name": "Ann" } })
+        );
+        assert_eq!(content["data"][1], json!({ "id": "2" }));
+        assert_eq!(content["pagination"]["limit"], 2);
+        assert!(content.get("policies").is_none());
+    }
+
+    #[test]
+    fn test_limit_response() {
+        let mut response = json!({
+            "structuredContent": { "data": { "id": "d1", "text": "ñandú and more" } }
+        });
+        limit_response(&mut response, 5);
+
+        let content = &response["structuredContent"];
+        let text = content["data"]["text"].as_str().unwrap();
+        assert!(text.starts_with("ñandú\n\n[… truncated after 5 of 14 characters"));
+        assert!(text.contains(
+            "get_document_chunk using id d1, split_by \"bytes\", chunk_size 7 and chunks 2 to 3"
+        ));
+        assert_eq!(content["truncated"], true);
+
+        let mut short = json!({ "structuredContent": { "data": [{ "text": "short" }] } });
+        limit_response(&mut short, 5);
+        assert_eq!(short["structuredContent"]["data"][0]["text"], "short");
+        assert!(short["structuredContent"].get("truncated").is_none());
+    }
+
+    #[test]
+    fn test_limit_response_search_results() {
+        let mut response = json!({
+            "structuredContent": {
+                "data": [{
+                    "context": "a very long context snippet",
+                    "ranking": 1.5,
+                    "document": { "id": "d1", "title": "Plan", "text": "a very long body" }
+                }]
+            }
+        });
+        limit_response(&mut response, 6);
+
+        let result = &response["structuredContent"]["data"][0];
+        let context = result["context"].as_str().unwrap();
+        assert!(context.starts_with("a very\n\n[… truncated after 6 of 27 characters]"));
+        let body = result["document"]["text"].as_str().unwrap();
+        assert!(body.contains("get_document_chunk using id d1"));
+        assert_eq!(result["document"]["title"], "Plan");
+        assert_eq!(response["structuredContent"]["truncated"], true);
+    }
+}
@@ -0,0 +1,203 @@
+//! Pin management tools
+//!
+//! Pins feature documents on a collection's front page, or on the home page
+//! when no collection is given.
+
+use serde_json::{json, Value};
+use tracing::debug;
+
+use super::common::{
+    create_mcp_success_response, get_optional_string_arg, get_string_arg, tool_definition,
+    with_optional_params,
+};
+use crate::error::{Error, Result};
+use crate::outline::Client as OutlineClient;
+
+/// Pins fetched per `pins.list` page
+const PINS_PAGE_SIZE: usize = 100;
+
+/// Get all pin tool definitions
+pub fn get_pin_tools() -> Vec<Value> {
+    vec![
+        with_optional_params(
+            tool_definition(
+                "create_pin",
+                "Pin document to a collection or the home page",
+                &[("document_id", "string", "Document ID")],
+            ),
+            &[
+                (
+                    "collection_id",
+                    "string",
+                    "Collection ID, omit to pin to the home page (optional)",
+                ),
+                (
+                    "index",
+                    "string",
+                    "Fractional sort index, e.g. from another pin (optional)",
+                ),
+            ],
+        ),
+        tool_definition(
+            "update_pin",
+            "Change pin position",
+            &[
+                ("id", "string", "Pin ID"),
+                ("index", "string", "New fractional sort index"),
+            ],
+        ),
+        with_optional_params(
+            tool_definition("delete_pin", "Unpin document by pin ID or document ID", &[]),
+            &[
+                ("id", "string", "Pin ID (optional)"),
+                ("document_id", "string", "Pinned document ID (optional)"),
+                (
+                    "collection_id",
+                    "string",
+                    "Collection the document is pinned to, omit for the home page (optional)",
+                ),
+            ],
+        ),
+        with_optional_params(
+            tool_definition(
+                "list_pins",
+                "List pins of a collection or the home page",
+                &[],
+            ),
+            &[(
+                "collection_id",
+                "string",
+                "Collection ID, omit for home page pins (optional)",
+            )],
+        ),
+    ]
+}
+
+/// Call pin tool
+pub async fn call_pin_tool(name: &str, arguments: Value, client: &OutlineClient) -> Result<Value> {
+    match name {
+        "create_pin" => create_pin(arguments, client).await,
+        "update_pin" => update_pin(arguments, client).await,
+        "delete_pin" => delete_pin(arguments, client).await,
+        "list_pins" => list_pins(arguments, client).await,
+        _ => unreachable!("Unknown pin tool: {}", name),
+    }
+}
+
+async fn create_pin(args: Value, client: &OutlineClient) -> Result<Value> {
+    let document_id = get_string_arg(&args, "document_id")?;
+    let collection_id = get_optional_string_arg(&args, "collection_id");
+    let index = get_optional_string_arg(&args, "index");
+
+    debug!("Pinning document {} to {:?}", document_id, collection_id);
+
+    let mut request_body = json!({ "documentId": document_id });
+    if let Some(cid) = collection_id {
+        request_body["collectionId"] = json!(cid);
+    }
+    if let Some(idx) = index {
+        request_body["index"] = json!(idx);
+    }
+
+    let response = client.post("pins.create", request_body).await?;
+
+    Ok(create_mcp_success_response(
+        "Pin created successfully",
+        Some(response),
+    ))
+}
+
+async fn update_pin(args: Value, client: &OutlineClient) -> Result<Value> {
+    let id = get_string_arg(&args, "id")?;
+    let index = get_string_arg(&args, "index")?;
+
+    debug!("Moving pin {} to index {}", id, index);
+
+    let request_body = json!({
+        "id": id,
+        "index": index
+    });
+    let response = client.post("pins.update", request_body).await?;
+
+    Ok(create_mcp_success_response(
+        "Pin updated successfully",
+        Some(response),
+    ))
+}
+
+async fn delete_pin(args: Value, client: &OutlineClient) -> Result<Value> {
+    let id = if let Some(id) = get_optional_string_arg(&args, "id") {
+        id
+    } else {
+        let document_id = get_optional_string_arg(&args, "document_id")
+            .ok_or_else(|| pin_error("Either 'id' or 'document_id' is required"))?;
+        let collection_id = get_optional_string_arg(&args, "collection_id");
+        find_pin_id(client, &document_id, collection_id.as_deref())
+            .await?
+            .ok_or_else(|| pin_error("Document is not pinned there"))?
+    };
+
+    debug!("Deleting pin: {}", id);
+
+    let response = client.post("pins.delete", json!({ "id": id })).await?;
+
+    Ok(create_mcp_success_response(
+        "Pin deleted successfully",
+        Some(response),
+    ))
+}
+
+async fn list_pins(args: Value, client: &OutlineClient) -> Result<Value> {
+    let collection_id = get_optional_string_arg(&args, "collection_id");
+
+    debug!("Listing pins for {:?}", collection_id);
+
+    let mut request_body = json!({});
+    if let Some(cid) = collection_id {
+        request_body["collectionId"] = json!(cid);
+    }
+
+    let response = client.post("pins.list", request_body).await?;
+
+    Ok(create_mcp_success_response(
+        "Pins listed successfully",
+        Some(response),
+    ))
+}
+
+/// Find the pin of `document_id` on a collection, or on the home page for `None`
+async fn find_pin_id(
+    client: &OutlineClient,
+    document_id: &str,
+    collection_id: Option<&str>,
+) -> Result<Option<String>> {
+    let mut offset = 0;
+    loop {
+        let mut request_body = json!({ "limit": PINS_PAGE_SIZE, "offset": offset });
+        if let Some(cid) = collection_id {
+            request_body["collectionId"] = json!(cid);
+        }
+
+        let response = client.post("pins.list", request_body).await?;
+        let page = response["data"].as_array().map_or(&[][..], Vec::as_slice);
+
+        if let Some(pin) = page
+            .iter()
+            .find(|pin| pin["documentId"].as_str() == Some(document_id))
+        {
+            return Ok(pin["id"].as_str().map(String::from));
+        }
+        if page.len() < PINS_PAGE_SIZE {
+            return Ok(None);
+        }
+        offset += page.len();
+    }
+}
+
+fn pin_error(message: &str) -> Error {
+    Error::Tool {
+        tool_name: "delete_pin".to_string(),
+        message: message.to_string(),
+        source: None,
+    }
+}
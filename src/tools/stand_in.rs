@@ -0,0 +1,84 @@
+//! Outline stand-in for tool tests
+//!
+//! Serves each API endpoint from a handler receiving the JSON request body, so
+//! that tools can be exercised end to end without an Outline instance.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+use crate::config::ApiKey;
+use crate::outline::Client as OutlineClient;
+
+/// Answer to one request: status, content type and body
+pub type Reply = (u16, &'static str, Vec<u8>);
+
+/// Handler for one endpoint
+pub type Handler = Arc<dyn Fn(&Value) -> Reply + Send + Sync>;
+
+/// JSON reply with status 200
+pub fn ok(body: &Value) -> Reply {
+    (200, "application/json", body.to_string().into_bytes())
+}
+
+/// Handler answering every request with the same JSON
+pub fn fixed(body: Value) -> Handler {
+    Arc::new(move |_| ok(&body))
+}
+
+/// Start a stand-in serving `routes` and return a client talking to it
+///
+/// Endpoints without a route answer 404.
+pub async fn serve(routes: Vec<(&'static str, Handler)>) -> OutlineClient {
+    let routes: Arc<HashMap<&'static str, Handler>> = Arc::new(routes.into_iter().collect());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let routes = Arc::clone(&routes);
+            tokio::spawn(async move {
+                let (read, mut write) = stream.into_split();
+                let mut reader = BufReader::new(read);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).await.unwrap();
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).await.unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).await.unwrap();
+
+                let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+                let endpoint = path.rsplit('/').next().unwrap_or_default();
+                let request = serde_json::from_slice(&body).unwrap_or(Value::Null);
+                let (status, content_type, reply) = routes.get(endpoint).map_or_else(
+                    || (404, "application/json", b"{\"ok\":false}".to_vec()),
+                    |handler| handler(&request),
+                );
+                let head = format!(
+                    "HTTP/1.1 {status} X\r\ncontent-type: {content_type}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                    reply.len()
+                );
+                let _ = write.write_all(head.as_bytes()).await;
+                let _ = write.write_all(&reply).await;
+            });
+        }
+    });
+
+    OutlineClient::from_parts(
+        reqwest::Client::new(),
+        ApiKey::new("ol_api_stand_in".to_string()).unwrap(),
+        format!("http://{addr}/api").parse().unwrap(),
+    )
+}
@@ -0,0 +1,210 @@
+//! Star management tools
+
+use serde_json::{json, Value};
+use tracing::debug;
+
+use super::common::{
+    create_mcp_success_response, get_optional_number_arg, get_optional_string_arg, tool_definition,
+    with_optional_params,
+};
+use crate::error::{Error, Result};
+use crate::outline::Client as OutlineClient;
+
+/// Stars fetched per `stars.list` page
+const STARS_PAGE_SIZE: usize = 100;
+
+/// Get all star tool definitions
+pub fn get_star_tools() -> Vec<Value> {
+    vec![
+        with_optional_params(
+            tool_definition("create_star", "Star document or collection", &[]),
+            &[
+                ("document_id", "string", "Document ID (optional)"),
+                ("collection_id", "string", "Collection ID (optional)"),
+            ],
+        ),
+        with_optional_params(
+            tool_definition(
+                "delete_star",
+                "Remove star by star ID, document ID or collection ID",
+                &[],
+            ),
+            &[
+                ("id", "string", "Star ID (optional)"),
+                ("document_id", "string", "Starred document ID (optional)"),
+                (
+                    "collection_id",
+                    "string",
+                    "Starred collection ID (optional)",
+                ),
+            ],
+        ),
+        with_optional_params(
+            tool_definition("list_stars", "List stars of the current user", &[]),
+            &[("limit", "number", "Number of stars (optional)")],
+        ),
+    ]
+}
+
+/// Call star tool
+pub async fn call_star_tool(name: &str, arguments: Value, client: &OutlineClient) -> Result<Value> {
+    match name {
+        "create_star" => create_star(arguments, client).await,
+        "delete_star" => delete_star(arguments, client).await,
+        "list_stars" => list_stars(arguments, client).await,
+        _ => unreachable!("Unknown star tool: {}", name),
+    }
+}
+
+async fn create_star(args: Value, client: &OutlineClient) -> Result<Value> {
+    let target = star_target(&args, "create_star")?;
+
+    debug!("Creating star: {}", target);
+
+    let response = client.post("stars.create", target).await?;
+
+    Ok(create_mcp_success_response(
+        "Star created successfully",
+        Some(response),
+    ))
+}
+
+async fn delete_star(args: Value, client: &OutlineClient) -> Result<Value> {
+    let id = if let Some(id) = get_optional_string_arg(&args, "id") {
+        id
+    } else {
+        let target = star_target(&args, "delete_star")?;
+        find_star_id(client, &target)
+            .await?
+            .ok_or_else(|| Error::Tool {
+                tool_name: "delete_star".to_string(),
+                message: "Item is not starred".to_string(),
+                source: None,
+            })?
+    };
+
+    debug!("Deleting star: {}", id);
+
+    let response = client.post("stars.delete", json!({ "id": id })).await?;
+
+    Ok(create_mcp_success_response(
+        "Star deleted successfully",
+        Some(response),
+    ))
+}
+
+async fn list_stars(args: Value, client: &OutlineClient) -> Result<Value> {
+    let limit = get_optional_number_arg(&args, "limit");
+
+    debug!("Listing stars");
+
+    let mut request_body = json!({});
+    if let Some(lim) = limit {
+        request_body["limit"] = json!(lim);
+    }
+
+    let response = client.post("stars.list", request_body).await?;
+
+    Ok(create_mcp_success_response(
+        "Stars listed successfully",
+        Some(response),
+    ))
+}
+
+/// Build the `documentId` / `collectionId` body identifying the starred item
+fn star_target(args: &Value, tool_name: &str) -> Result<Value> {
+    match (
+        get_optional_string_arg(args, "document_id"),
+        get_optional_string_arg(args, "collection_id"),
+    ) {
+        (Some(document_id), None) => Ok(json!({ "documentId": document_id })),
+        (None, Some(collection_id)) => Ok(json!({ "collectionId": collection_id })),
+        _ => Err(Error::Tool {
+            tool_name: tool_name.to_string(),
+            message: "Exactly one of 'document_id' or 'collection_id' is required".to_string(),
+            source: None,
+        }),
+    }
+}
+
+/// Find the star pointing at `target` among the current user's stars
+async fn find_star_id(client: &OutlineClient, target: &Value) -> Result<Option<String>> {
+    let mut offset = 0;
+    loop {
+        let response = client
+            .post(
+                "stars.list",
+                json!({ "limit": STARS_PAGE_SIZE, "offset": offset }),
+            )
+            .await?;
+        let page = response["data"].as_array().map_or(&[][..], Vec::as_slice);
+
+        let found = page.iter().find(|star| {
+            target
+                .as_object()
+                .is_some_and(|t| t.iter().all(|(key, value)| &star[key] == value))
+        });
+        if let Some(star) = found {
+            return Ok(star["id"].as_str().map(String::from));
+        }
+        if page.len() < STARS_PAGE_SIZE {
+            return Ok(None);
+        }
+        offset += page.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::stand_in;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_star_tools_require_no_target() {
+        for tool in get_star_tools() {
+            assert_eq!(tool["inputSchema"]["required"], json!([]));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_star_beyond_first_page() {
+        let client = stand_in::serve(vec![(
+            "stars.list",
+            Arc::new(|body: &Value| {
+                let offset = body["offset"].as_u64().unwrap_or(0);
+                let page: Vec<Value> = if offset == 0 {
+                    (0..STARS_PAGE_SIZE)
+                        .map(|i| json!({ "id": format!("star-{i}"), "documentId": format!("doc-{i}") }))
+                        .collect()
+                } else {
+                    vec![json!({ "id": "star-last", "documentId": "doc-last" })]
+                };
+                stand_in::ok(&json!({ "data": page }))
+            }),
+        )])
+        .await;
+
+        let target = json!({ "documentId": "doc-last" });
+        assert_eq!(
+            find_star_id(&client, &target).await.unwrap().as_deref(),
+            Some("star-last")
+        );
+        let missing = json!({ "documentId": "doc-none" });
+        assert_eq!(find_star_id(&client, &missing).await.unwrap(), None);
+    }
+
+    #[test]
+    fn test_star_target() {
+        assert_eq!(
+            star_target(&json!({ "document_id": "d1" }), "t").unwrap(),
+            json!({ "documentId": "d1" })
+        );
+        assert_eq!(
+            star_target(&json!({ "collection_id": "c1" }), "t").unwrap(),
+            json!({ "collectionId": "c1" })
+        );
+        assert!(star_target(&json!({}), "t").is_err());
+        assert!(star_target(&json!({ "document_id": "d1", "collection_id": "c1" }), "t").is_err());
+    }
+}
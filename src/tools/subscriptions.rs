@@ -0,0 +1,83 @@
+//! Document subscription tools
+
+use serde_json::{json, Value};
+use tracing::debug;
+
+use super::common::{create_mcp_success_response, get_string_arg, tool_definition};
+use crate::error::{Error, Result};
+use crate::outline::Client as OutlineClient;
+
+/// Event the API user subscribes to
+const SUBSCRIPTION_EVENT: &str = "documents.update";
+
+/// Get all subscription tool definitions
+pub fn get_subscription_tools() -> Vec<Value> {
+    vec![
+        tool_definition(
+            "subscribe_to_document",
+            "Subscribe to change notifications for a document",
+            &[("document_id", "string", "Document ID")],
+        ),
+        tool_definition(
+            "unsubscribe_from_document",
+            "Stop change notifications for a document",
+            &[("document_id", "string", "Document ID")],
+        ),
+    ]
+}
+
+/// Call subscription tool
+pub async fn call_subscription_tool(
+    name: &str,
+    arguments: Value,
+    client: &OutlineClient,
+) -> Result<Value> {
+    match name {
+        "subscribe_to_document" => subscribe_to_document(arguments, client).await,
+        "unsubscribe_from_document" => unsubscribe_from_document(arguments, client).await,
+        _ => unreachable!("Unknown subscription tool: {}", name),
+    }
+}
+
+async fn subscribe_to_document(args: Value, client: &OutlineClient) -> Result<Value> {
+    let document_id = get_string_arg(&args, "document_id")?;
+
+    debug!("Subscribing to document: {}", document_id);
+
+    let request_body = json!({
+        "documentId": document_id,
+        "event": SUBSCRIPTION_EVENT
+    });
+    let response = client.post("subscriptions.create", request_body).await?;
+
+    Ok(create_mcp_success_response(
+        "Subscribed successfully",
+        Some(response),
+    ))
+}
+
+async fn unsubscribe_from_document(args: Value, client: &OutlineClient) -> Result<Value> {
+    let document_id = get_string_arg(&args, "document_id")?;
+
+    debug!("Unsubscribing from document: {}", document_id);
+
+    let request_body = json!({
+        "documentId": document_id,
+        "event": SUBSCRIPTION_EVENT
+    });
+    let existing = client.post("subscriptions.info", request_body).await?;
+    let id = existing["data"]["id"].as_str().ok_or_else(|| Error::Tool {
+        tool_name: "unsubscribe_from_document".to_string(),
+        message: format!("Not subscribed to document {document_id}"),
+        source: None,
+    })?;
+
+    let response = client
+        .post("subscriptions.delete", json!({ "id": id }))
+        .await?;
+
+    Ok(create_mcp_success_response(
+        "Unsubscribed successfully",
+        Some(response),
+    ))
+}
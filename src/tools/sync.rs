@@ -0,0 +1,78 @@
+//! Local directory sync tool (STDIO mode only)
+
+use serde_json::{json, Value};
+use tracing::debug;
+
+use super::common::{
+    create_mcp_success_response, ensure_local_files_allowed, get_optional_bool_arg, get_string_arg,
+    tool_definition,
+};
+use crate::error::Result;
+use crate::mcp::Transport;
+use crate::outline::Client as OutlineClient;
+use crate::sync::{sync_collection as run_sync, SyncAction, SyncOptions};
+
+/// Get all sync tool definitions
+pub fn get_sync_tools() -> Vec<Value> {
+    vec![tool_definition(
+        "sync_collection",
+        "Mirror a collection to a local folder of markdown files and push local edits back",
+        &[
+            ("collection_id", "string", "Collection ID"),
+            ("directory", "string", "Local directory path"),
+            (
+                "dry_run",
+                "boolean",
+                "Only report planned changes (optional)",
+            ),
+        ],
+    )]
+}
+
+/// Call sync tool
+pub async fn call_sync_tool(
+    name: &str,
+    arguments: Value,
+    client: &OutlineClient,
+    transport: Transport,
+) -> Result<Value> {
+    match name {
+        "sync_collection" => sync_collection(arguments, client, transport).await,
+        _ => unreachable!("Unknown sync tool: {}", name),
+    }
+}
+
+async fn sync_collection(
+    args: Value,
+    client: &OutlineClient,
+    transport: Transport,
+) -> Result<Value> {
+    ensure_local_files_allowed(transport, "sync_collection", "directory")?;
+
+    let collection_id = get_string_arg(&args, "collection_id")?;
+    let directory = get_string_arg(&args, "directory")?;
+    let dry_run = get_optional_bool_arg(&args, "dry_run").unwrap_or(false);
+
+    debug!("Syncing collection {} to {}", collection_id, directory);
+
+    let options = SyncOptions {
+        collection_id,
+        directory: directory.into(),
+        dry_run,
+    };
+    let entries = run_sync(client, &options).await?;
+
+    let conflicts = entries
+        .iter()
+        .filter(|e| e.action == SyncAction::Conflict)
+        .count();
+
+    Ok(create_mcp_success_response(
+        "Sync completed",
+        Some(json!({
+            "dryRun": dry_run,
+            "conflicts": conflicts,
+            "data": entries.iter().map(crate::sync::SyncEntry::to_json).collect::<Vec<_>>()
+        })),
+    ))
+}
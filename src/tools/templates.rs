@@ -0,0 +1,420 @@
+//! Template tools
+//!
+//! Templates are documents flagged as templates. Creating a document from one
+//! substitutes caller-supplied `{{variables}}` and Outline's built-in
+//! `{date}`, `{time}`, `{datetime}` and `{author}` placeholders.
+
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use serde_json::{json, Map, Value};
+use tracing::debug;
+
+use super::common::{
+    civil_from_days, create_mcp_success_response, get_optional_bool_arg, get_optional_number_arg,
+    get_optional_string_arg, get_string_arg, tool_definition, unix_now,
+};
+use crate::error::{Error, Result};
+use crate::outline::Client as OutlineClient;
+
+/// Get all template tool definitions
+pub fn get_template_tools() -> Vec<Value> {
+    vec![
+        tool_definition(
+            "list_templates",
+            "List document templates",
+            &[
+                (
+                    "collection_id",
+                    "string",
+                    "Only templates of this collection (optional)",
+                ),
+                ("limit", "number", "Number of templates (optional)"),
+            ],
+        ),
+        tool_definition(
+            "get_template",
+            "Get template by ID, including the variables it uses",
+            &[("id", "string", "Template ID")],
+        ),
+        tool_definition(
+            "create_document_from_template",
+            "Create document from template, filling in {{variables}} and {date}, {time}, {datetime}, {author}",
+            &[
+                ("template_id", "string", "Template ID"),
+                (
+                    "variables",
+                    "object",
+                    "Values for {{name}} placeholders, e.g. {\"week\": \"42\"} (optional)",
+                ),
+                (
+                    "title",
+                    "string",
+                    "Document title, default is the template title with variables filled in (optional)",
+                ),
+                (
+                    "collection_id",
+                    "string",
+                    "Target collection ID, default is the template's collection (optional)",
+                ),
+                (
+                    "parent_document_id",
+                    "string",
+                    "Parent document ID to nest under (optional)",
+                ),
+                ("publish", "boolean", "Publish immediately (optional)"),
+            ],
+        ),
+    ]
+}
+
+/// Call template tool
+pub async fn call_template_tool(
+    name: &str,
+    arguments: Value,
+    client: &OutlineClient,
+) -> Result<Value> {
+    match name {
+        "list_templates" => list_templates(arguments, client).await,
+        "get_template" => get_template(arguments, client).await,
+        "create_document_from_template" => create_document_from_template(arguments, client).await,
+        _ => unreachable!("Unknown template tool: {}", name),
+    }
+}
+
+async fn list_templates(args: Value, client: &OutlineClient) -> Result<Value> {
+    let collection_id = get_optional_string_arg(&args, "collection_id");
+    let limit = get_optional_number_arg(&args, "limit");
+
+    debug!("Listing templates for {:?}", collection_id);
+
+    let mut request_body = json!({ "template": true });
+    if let Some(cid) = collection_id {
+        request_body["collectionId"] = json!(cid);
+    }
+    if let Some(lim) = limit {
+        request_body["limit"] = json!(lim);
+    }
+
+    let response = client.post("documents.list", request_body).await?;
+
+    Ok(create_mcp_success_response(
+        "Templates listed successfully",
+        Some(response),
+    ))
+}
+
+async fn get_template(args: Value, client: &OutlineClient) -> Result<Value> {
+    let id = get_string_arg(&args, "id")?;
+
+    debug!("Getting template: {}", id);
+
+    let mut response = fetch_template(client, &id, "get_template").await?;
+
+    let text = response["data"]["text"].as_str().unwrap_or_default();
+    let title = response["data"]["title"].as_str().unwrap_or_default();
+    let variables: BTreeSet<String> = template_variables(title)
+        .into_iter()
+        .chain(template_variables(text))
+        .collect();
+    response["variables"] = json!(variables);
+
+    Ok(create_mcp_success_response(
+        "Template retrieved successfully",
+        Some(response),
+    ))
+}
+
+async fn create_document_from_template(args: Value, client: &OutlineClient) -> Result<Value> {
+    let template_id = get_string_arg(&args, "template_id")?;
+    let title = get_optional_string_arg(&args, "title");
+    let collection_id = get_optional_string_arg(&args, "collection_id");
+    let parent_document_id = get_optional_string_arg(&args, "parent_document_id");
+    let publish = get_optional_bool_arg(&args, "publish");
+
+    let mut variables = args
+        .get("variables")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+
+    debug!("Creating document from template: {}", template_id);
+
+    let template = fetch_template(client, &template_id, "create_document_from_template").await?;
+    let template = &template["data"];
+
+    // Only look up the author when the template actually uses it
+    let template_text = template["text"].as_str().unwrap_or_default();
+    let template_title = template["title"].as_str().unwrap_or_default();
+    if [template_text, template_title]
+        .iter()
+        .any(|s| s.contains("{author}"))
+        && !variables.contains_key("author")
+    {
+        let auth = client.post("auth.info", json!({})).await?;
+        if let Some(name) = auth["data"]["user"]["name"].as_str() {
+            variables.insert("author".to_string(), json!(name));
+        }
+    }
+    add_builtin_variables(&mut variables, unix_now());
+
+    let text = fill_template(template_text, &variables);
+    let title = title.unwrap_or_else(|| fill_template(template_title, &variables));
+    let unresolved: BTreeSet<String> = template_variables(&title)
+        .into_iter()
+        .chain(template_variables(&text))
+        .collect();
+
+    // No `templateId`: Outline would then build the document from the
+    // template's own title and text and drop the substitutions
+    let mut request_body = json!({
+        "title": title,
+        "text": text
+    });
+    match (collection_id, template["collectionId"].as_str()) {
+        (Some(cid), _) => request_body["collectionId"] = json!(cid),
+        (None, Some(cid)) => request_body["collectionId"] = json!(cid),
+        (None, None) => {}
+    }
+    if let Some(pid) = parent_document_id {
+        request_body["parentDocumentId"] = json!(pid);
+    }
+    if let Some(p) = publish {
+        request_body["publish"] = json!(p);
+    }
+
+    let mut response = client.post("documents.create", request_body).await?;
+    if !unresolved.is_empty() {
+        response["unresolvedVariables"] = json!(unresolved);
+    }
+
+    Ok(create_mcp_success_response(
+        "Document created from template successfully",
+        Some(response),
+    ))
+}
+
+/// Fetch a document and make sure it is a template
+async fn fetch_template(client: &OutlineClient, id: &str, tool_name: &str) -> Result<Value> {
+    let response = client.post("documents.info", json!({ "id": id })).await?;
+
+    if response["data"]["template"].as_bool() != Some(true) {
+        return Err(Error::Tool {
+            tool_name: tool_name.to_string(),
+            message: format!("Document {id} is not a template"),
+            source: None,
+        });
+    }
+
+    Ok(response)
+}
+
+/// A `{{name}}` or built-in `{name}` placeholder found in template text
+struct Placeholder<'a> {
+    /// Byte range of the placeholder including its braces
+    range: Range<usize>,
+    /// Variable name with surrounding whitespace trimmed
+    name: &'a str,
+}
+
+/// All placeholders in `text`, in order
+///
+/// The single tokenizer behind listing and filling, so both agree on what a
+/// placeholder is.
+fn placeholders(text: &str) -> Vec<Placeholder<'_>> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+
+    while let Some(start) = text[offset..].find('{').map(|i| offset + i) {
+        let after = &text[start..];
+        let (open, close) = if after.starts_with("{{") {
+            ("{{", "}}")
+        } else {
+            ("{", "}")
+        };
+        let inner = &after[open.len()..];
+
+        match inner.find(close) {
+            Some(end) if is_variable_name(inner[..end].trim()) => {
+                let name = inner[..end].trim();
+                let stop = start + open.len() + end + close.len();
+                // Single braces are only placeholders for Outline's built-ins
+                if open == "{{" || BUILTIN_VARIABLES.contains(&name) {
+                    found.push(Placeholder {
+                        range: start..stop,
+                        name,
+                    });
+                }
+                offset = stop;
+            }
+            _ => offset = start + 1,
+        }
+    }
+
+    found
+}
+
+/// Names of all `{{name}}` and built-in `{name}` placeholders in `text`
+fn template_variables(text: &str) -> Vec<String> {
+    placeholders(text)
+        .into_iter()
+        .map(|placeholder| placeholder.name.to_string())
+        .collect()
+}
+
+/// Placeholders Outline fills in when a template is used from the editor
+const BUILTIN_VARIABLES: [&str; 4] = ["date", "time", "datetime", "author"];
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ' '))
+}
+
+/// Replace placeholders with values from `variables`, leaving unknown ones intact
+fn fill_template(text: &str, variables: &Map<String, Value>) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for placeholder in placeholders(text) {
+        let Some(value) = variables.get(placeholder.name) else {
+            continue;
+        };
+        output.push_str(&text[last..placeholder.range.start]);
+        output.push_str(value.as_str().map_or(&value.to_string(), |s| s));
+        last = placeholder.range.end;
+    }
+
+    output.push_str(&text[last..]);
+    output
+}
+
+/// Add `date`, `time` and `datetime` (UTC) unless the caller supplied them
+fn add_builtin_variables(variables: &mut Map<String, Value>, unix_secs: u64) {
+    let days = unix_secs / 86_400;
+    let secs_of_day = unix_secs % 86_400;
+    let (year, month, day) = civil_from_days(days);
+
+    let date = format!("{year:04}-{month:02}-{day:02}");
+    let time = format!("{:02}:{:02}", secs_of_day / 3600, secs_of_day % 3600 / 60);
+
+    for (name, value) in [
+        ("datetime", format!("{date} {time}")),
+        ("date", date),
+        ("time", time),
+    ] {
+        variables.entry(name).or_insert_with(|| json!(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::stand_in;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_template_variables() {
+        let text = "# Week {{ week }} by {author}\n{{team}} on {date}, {not_builtin} {{}} {x";
+        assert_eq!(
+            template_variables(text),
+            vec!["week", "author", "team", "date"]
+        );
+    }
+
+    #[test]
+    fn test_fill_template() {
+        let variables = json!({ "week": 42, "team": "Core", "author": "Ann" });
+        let variables = variables.as_object().unwrap();
+
+        assert_eq!(
+            fill_template(
+                "Week {{week}} ({{ team }}) by {author}, {{missing}} {json}",
+                variables
+            ),
+            "Week 42 (Core) by Ann, {{missing}} {json}"
+        );
+        // Caller variables are not applied to single-brace placeholders
+        assert_eq!(fill_template("{team}", variables), "{team}");
+    }
+
+    #[test]
+    fn test_listing_and_filling_agree() {
+        let text = "{{ a }} {{b {{c}} {date} {{d+e}} {{ f}";
+        let names = template_variables(text);
+        assert_eq!(names, vec!["a", "c", "date"]);
+
+        // Every listed name is filled, nothing else is touched
+        let variables: Map<String, Value> = ["a", "b", "c", "date", "d+e", "f"]
+            .iter()
+            .map(|name| ((*name).to_string(), json!("X")))
+            .collect();
+        assert_eq!(fill_template(text, &variables), "X {{b X X {{d+e}} {{ f}");
+    }
+
+    #[test]
+    fn test_add_builtin_variables() {
+        let mut variables = Map::new();
+        variables.insert("time".to_string(), json!("morning"));
+        // 2024-02-29 13:45:00 UTC
+        add_builtin_variables(&mut variables, 1_709_214_300);
+
+        assert_eq!(variables["date"], "2024-02-29");
+        assert_eq!(variables["time"], "morning");
+        assert_eq!(variables["datetime"], "2024-02-29 13:45");
+    }
+
+    #[tokio::test]
+    async fn test_create_from_template_sends_substituted_text() {
+        let created = Arc::new(Mutex::new(Value::Null));
+        let seen = Arc::clone(&created);
+        let client = stand_in::serve(vec![
+            (
+                "documents.info",
+                stand_in::fixed(json!({ "data": {
+                    "id": "tpl-1",
+                    "template": true,
+                    "title": "Week {{week}}",
+                    "text": "Notes for week {{week}}",
+                    "collectionId": "col-1"
+                }})),
+            ),
+            (
+                "documents.create",
+                Arc::new(move |body: &Value| {
+                    *seen.lock().unwrap() = body.clone();
+                    stand_in::ok(&json!({ "data": { "id": "doc-1" } }))
+                }),
+            ),
+        ])
+        .await;
+
+        create_document_from_template(
+            json!({ "template_id": "tpl-1", "variables": { "week": 42 } }),
+            &client,
+        )
+        .await
+        .unwrap();
+
+        let body = created.lock().unwrap().clone();
+        assert_eq!(body["title"], "Week 42");
+        assert_eq!(body["text"], "Notes for week 42");
+        assert_eq!(body["collectionId"], "col-1");
+        assert!(body.get("templateId").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_plain_document_is_not_a_template() {
+        let client = stand_in::serve(vec![(
+            "documents.info",
+            stand_in::fixed(json!({ "data": { "id": "doc-1", "title": "Notes" } })),
+        )])
+        .await;
+
+        let error = fetch_template(&client, "doc-1", "get_template")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("not a template"), "{error}");
+    }
+}
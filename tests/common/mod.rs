@@ -1,5 +1,6 @@
 //! Common test utilities
 
+use outline_mcp_rs::config::TransportConfig;
 use outline_mcp_rs::{Config, Error, Result};
 use serde_json::{json, Value};
 
@@ -18,6 +19,18 @@ pub fn create_test_config() -> Config {
         http_session_timeout: 1800,
         http_rate_limit: 60,
         mcp_auth_tokens: vec!["test-mcp-token".to_string()],
+        max_result_chars: None,
+        cache_enabled: false,
+        cache_ttls: vec![],
+        max_concurrency: 32,
+        max_concurrency_per_key: 8,
+        queue_timeout: 10,
+        breaker_threshold: 5,
+        breaker_cooldown: 30,
+        ready_api_key: None,
+        ready_cache_ttl: 10,
+        metrics_token: None,
+        transport: TransportConfig::default(),
     }
 }
 
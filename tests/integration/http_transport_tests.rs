@@ -31,7 +31,7 @@ async fn test_config_for_http_mode() {
 
 #[tokio::test]
 async fn test_http_mode_requires_mcp_auth_tokens() {
-    use outline_mcp_rs::config::{LogLevel, Port};
+    use outline_mcp_rs::config::{LogLevel, Port, TransportConfig};
     use outline_mcp_rs::Config;
     use std::net::IpAddr;
 
@@ -45,6 +45,18 @@ async fn test_http_mode_requires_mcp_auth_tokens() {
         http_session_timeout: 1800,
         http_rate_limit: 60,
         mcp_auth_tokens: vec![], // Empty — should fail
+        max_result_chars: None,
+        cache_enabled: false,
+        cache_ttls: vec![],
+        max_concurrency: 32,
+        max_concurrency_per_key: 8,
+        queue_timeout: 10,
+        breaker_threshold: 5,
+        breaker_cooldown: 30,
+        ready_api_key: None,
+        ready_cache_ttl: 10,
+        metrics_token: None,
+        transport: TransportConfig::default(),
     };
 
     // run_http should fail when mcp_auth_tokens is empty
@@ -83,7 +95,7 @@ async fn test_mcp_tools_call_request_format() {
 
 #[tokio::test]
 async fn test_stdio_mode_requires_outline_api_key() {
-    use outline_mcp_rs::config::{LogLevel, Port};
+    use outline_mcp_rs::config::{LogLevel, Port, TransportConfig};
     use outline_mcp_rs::Config;
     use std::net::IpAddr;
 
@@ -97,6 +109,18 @@ async fn test_stdio_mode_requires_outline_api_key() {
         http_session_timeout: 1800,
         http_rate_limit: 60,
         mcp_auth_tokens: vec![],
+        max_result_chars: None,
+        cache_enabled: false,
+        cache_ttls: vec![],
+        max_concurrency: 32,
+        max_concurrency_per_key: 8,
+        queue_timeout: 10,
+        breaker_threshold: 5,
+        breaker_cooldown: 30,
+        ready_api_key: None,
+        ready_cache_ttl: 10,
+        metrics_token: None,
+        transport: TransportConfig::default(),
     };
 
     let result = outline_mcp_rs::run_stdio(config).await;